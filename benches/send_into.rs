@@ -0,0 +1,87 @@
+//! Compare `IppRequest::send_cloned` with `IppRequest::send_into`
+//!
+//! Polls the default destination, or the one named by `CUPS_BENCH_PRINTER`,
+//! with the same Get-Printer-Attributes request. Skips when no CUPS server or
+//! printer is available.
+
+use cups_rs::{
+    IppOperation, IppRequest, IppResponse, IppTag, IppValueTag, get_default_destination,
+    get_destination,
+};
+use std::env;
+use std::time::{Duration, Instant};
+
+const WARMUP: u32 = 20;
+const ITERATIONS: u32 = 500;
+
+fn main() {
+    let printer = match env::var("CUPS_BENCH_PRINTER") {
+        Ok(name) => get_destination(&name),
+        Err(_) => get_default_destination(),
+    };
+    let printer = match printer {
+        Ok(printer) => printer,
+        Err(e) => {
+            println!("No printer to benchmark against - skipping ({})", e);
+            return;
+        }
+    };
+
+    let (mut request, connection) = match printer.new_request(IppOperation::GetPrinterAttributes) {
+        Ok(pair) => pair,
+        Err(e) => {
+            println!("Could not connect to {} - skipping ({})", printer.name, e);
+            return;
+        }
+    };
+    add_attributes(&mut request);
+    let resource = connection.resource_path().to_string();
+
+    let cloned = measure(|| {
+        request
+            .send_cloned(&connection, &resource)
+            .expect("send_cloned failed");
+    });
+
+    let mut response: Option<IppResponse> = None;
+    let reused = measure(|| {
+        request
+            .send_into(&connection, &resource, &mut response)
+            .expect("send_into failed");
+    });
+
+    println!("{} requests to {}:", ITERATIONS, printer.name);
+    println!("  send_cloned: {:?} per request", cloned);
+    println!("  send_into:   {:?} per request", reused);
+}
+
+/// Request the attributes a status monitor typically polls
+fn add_attributes(request: &mut IppRequest) {
+    request
+        .add_strings(
+            IppTag::Operation,
+            IppValueTag::Keyword,
+            "requested-attributes",
+            &[
+                "printer-state",
+                "printer-state-reasons",
+                "printer-state-message",
+                "printer-is-accepting-jobs",
+                "queued-job-count",
+            ],
+        )
+        .expect("Failed to add requested-attributes");
+}
+
+/// Average time per call after a short warm-up
+fn measure(mut send: impl FnMut()) -> Duration {
+    for _ in 0..WARMUP {
+        send();
+    }
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        send();
+    }
+    start.elapsed() / ITERATIONS
+}
@@ -9,9 +9,10 @@ fn main() -> Result<()> {
 
     // Set up a password callback for GUI-style authentication
     println!("Setting up password callback...");
-    set_password_callback(Some(Box::new(|prompt, _http, method, resource| {
+    set_password_callback(Some(Box::new(|prompt, server, method, resource| {
         println!("Authentication required!");
         println!("Prompt: {}", prompt);
+        println!("Server: {}", server.unwrap_or("default"));
         println!("Method: {}", method);
         println!("Resource: {}", resource);
         
@@ -42,8 +42,8 @@ fn handle_cancel_command(args: &[String]) -> Result<()> {
 
 fn handle_list_command() -> Result<()> {
     // Get different job queues
-    let active_jobs = get_active_jobs(None)?;
-    let completed_jobs = get_completed_jobs(None)?;
+    let active_jobs = get_active_jobs(None, None)?;
+    let completed_jobs = get_completed_jobs(None, None)?;
 
     println!("Active jobs: {}", active_jobs.len());
     for job in &active_jobs {
@@ -99,11 +99,10 @@ fn handle_print_workflow(args: &[String]) -> Result<()> {
     job.close()?;
     println!("Job closed - printing started");
 
-    std::thread::sleep(std::time::Duration::from_secs(1));
-
-    // Step 5: Final status check
-    match get_job_info(job.id) {
-        Ok(info) => println!("Final status: {}", info.status),
+    // Step 5: Wait for the job to finish
+    match job.wait_for_completion(Some(std::time::Duration::from_secs(30))) {
+        Ok(status) => println!("Final status: {}", status),
+        Err(Error::Timeout) => println!("Job still in progress after 30 seconds"),
         Err(_) => println!("Job completed and removed from queue"),
     }
 
@@ -1,6 +1,6 @@
 use cups_rs::{
     get_all_destinations, get_default_destination, find_destinations, 
-    Destinations, PRINTER_LOCAL, PRINTER_REMOTE, Result,
+    Destinations, PRINTER_LOCAL, PRINTER_REMOTE, Result, spool,
 };
 
 fn main() -> Result<()> {
@@ -20,24 +20,19 @@ fn main() -> Result<()> {
 
     println!("\nBasic printer information:");
     for (i, dest) in destinations.iter().enumerate() {
-        println!("\nPrinter #{}: {}", i + 1, dest.name);
+        println!("\nPrinter #{}: {}", i + 1, dest.summary());
 
+        let caps = dest.capabilities();
         println!(
-            "  State: {} | Accepting jobs: {}",
-            dest.state(),
-            dest.is_accepting_jobs()
+            "  Color: {} | Duplex: {} | Staple: {} | Remote: {}",
+            caps.is_color(),
+            caps.is_duplex(),
+            caps.can_staple(),
+            caps.is_remote()
         );
 
-        if let Some(info) = dest.info() {
-            println!("  Description: {}", info);
-        }
-        if let Some(location) = dest.location() {
-            println!("  Location: {}", location);
-        }
-
-        let reasons = dest.state_reasons();
-        if !reasons.is_empty() {
-            println!("  Issues: {}", reasons.join(", "));
+        if let Some(output) = spool::file_device_path(dest) {
+            println!("  File device output: {}", output.display());
         }
     }
 
@@ -1,6 +1,4 @@
-use cups_rs::{
-    create_job, get_destination, get_job_info, Result, FORMAT_TEXT,
-};
+use cups_rs::{create_job, get_destination, Result, FORMAT_TEXT};
 
 fn main() -> Result<()> {
     println!("CUPS Multi-Document Job Example");
@@ -34,10 +32,8 @@ fn main() -> Result<()> {
     println!("All documents submitted. Closing job to start printing...");
     job.close()?;
 
-    std::thread::sleep(std::time::Duration::from_secs(1));
-
-    match get_job_info(job.id) {
-        Ok(info) => println!("Job status: {} ({} bytes)", info.status, info.size),
+    match job.wait_for_completion(Some(std::time::Duration::from_secs(30))) {
+        Ok(status) => println!("Job status: {}", status),
         Err(_) => println!("Job completed and removed from queue"),
     }
 
@@ -1,5 +1,4 @@
 use cups_rs::*;
-use std::ptr;
 
 fn main() -> Result<()> {
     println!("CUPS Printer Capabilities Example");
@@ -9,7 +8,6 @@ fn main() -> Result<()> {
 
     // Get printer and its detailed capabilities
     let destination = get_destination(&printer_name)?;
-    let info = destination.get_detailed_info(ptr::null_mut())?;
 
     println!(
         "Printer: {} ({})",
@@ -28,7 +26,7 @@ fn main() -> Result<()> {
 
     println!("\nSupported options:");
     for (option, description) in &options {
-        let supported = destination.is_option_supported(ptr::null_mut(), option);
+        let supported = destination.supports_option(option)?;
         println!(
             "  {}: {}",
             description,
@@ -45,13 +43,12 @@ fn main() -> Result<()> {
 
     println!("\nMedia support:");
     for (media, name) in &media_types {
-        let supported =
-            info.is_value_supported(ptr::null_mut(), destination.as_ptr(), MEDIA, media);
+        let supported = destination.supports_value(MEDIA, media)?;
         println!("  {}: {}", name, if supported { "Yes" } else { "No" });
     }
 
     // Get all available media sizes
-    match info.get_all_media(ptr::null_mut(), destination.as_ptr(), MEDIA_FLAGS_DEFAULT) {
+    match destination.all_media() {
         Ok(sizes) => {
             println!("\nAvailable media ({} total):", sizes.len());
             for size in sizes.iter().take(5) {
@@ -70,9 +67,7 @@ fn main() -> Result<()> {
     }
 
     // Get default media with detailed margins
-    if let Ok(default_media) =
-        info.get_default_media(ptr::null_mut(), destination.as_ptr(), MEDIA_FLAGS_DEFAULT)
-    {
+    if let Ok(default_media) = destination.default_media() {
         println!("\nDefault media: {}", default_media.name);
         println!(
             "  Printable area: {:.1}\" x {:.1}\"",
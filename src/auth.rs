@@ -1,9 +1,12 @@
 use crate::bindings;
+use crate::config::set_user;
+use crate::connection::http_hostname;
 use crate::error::{Error, Result};
 use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
+use std::cell::RefCell;
 use std::ptr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, PoisonError};
 
 /// Password callback function type
 /// 
@@ -11,7 +14,7 @@ use std::sync::Arc;
 /// 
 /// # Parameters
 /// - `prompt`: The authentication prompt string
-/// - `http_connection`: Optional HTTP connection (None for CUPS_HTTP_DEFAULT)
+/// - `server`: Hostname of the server that requested credentials, if known
 /// - `method`: HTTP method ("GET", "POST", "PUT", etc.)
 /// - `resource`: The resource path being accessed
 /// 
@@ -45,14 +48,51 @@ pub type ClientCertCallback = dyn Fn(&str) -> Option<Vec<u8>> + Send + Sync;
 /// - `false`: Reject the certificate
 pub type ServerCertCallback = dyn Fn(&str, &[u8]) -> bool + Send + Sync;
 
-// Thread-local storage for authentication callbacks
+// Process-wide storage for authentication callbacks, so the C wrapper finds
+// them no matter which thread CUPS invokes it on
+static PASSWORD_CALLBACK: Mutex<Option<Arc<PasswordCallback>>> = Mutex::new(None);
+static CLIENT_CERT_CALLBACK: Mutex<Option<Arc<ClientCertCallback>>> = Mutex::new(None);
+static SERVER_CERT_CALLBACK: Mutex<Option<Arc<ServerCertCallback>>> = Mutex::new(None);
+
 thread_local! {
-    static PASSWORD_CALLBACK: std::cell::RefCell<Option<Arc<PasswordCallback>>> = 
-        const { std::cell::RefCell::new(None) };
-    static CLIENT_CERT_CALLBACK: std::cell::RefCell<Option<Arc<ClientCertCallback>>> = 
-        const { std::cell::RefCell::new(None) };
-    static SERVER_CERT_CALLBACK: std::cell::RefCell<Option<Arc<ServerCertCallback>>> = 
-        const { std::cell::RefCell::new(None) };
+    // Password most recently handed to CUPS on this thread, kept alive until
+    // the next callback on the same thread
+    static LAST_PASSWORD: RefCell<Option<CString>> = const { RefCell::new(None) };
+
+    // Credentials from set_credentials, which belong to the user set on this
+    // thread and so are never offered to prompts on other threads
+    static CREDENTIALS: RefCell<Option<Credentials>> = const { RefCell::new(None) };
+}
+
+/// A fixed password that answers a single prompt
+struct Credentials {
+    password: String,
+    answered: bool,
+}
+
+/// Register the C wrapper with CUPS for the calling thread, or remove it
+fn register_password_wrapper(enabled: bool) {
+    unsafe {
+        if enabled {
+            bindings::cupsSetPasswordCB2(Some(password_callback_wrapper), ptr::null_mut());
+        } else {
+            bindings::cupsSetPasswordCB2(None, ptr::null_mut());
+        }
+    }
+}
+
+fn has_credentials() -> bool {
+    CREDENTIALS.with(|credentials| credentials.borrow().is_some())
+}
+
+/// Replace a stored callback, ignoring poisoning since the slot is always valid
+fn store_callback<T: ?Sized>(slot: &Mutex<Option<Arc<T>>>, callback: Option<Arc<T>>) {
+    *slot.lock().unwrap_or_else(PoisonError::into_inner) = callback;
+}
+
+/// Get the stored callback without holding the lock while it runs
+fn load_callback<T: ?Sized>(slot: &Mutex<Option<Arc<T>>>) -> Option<Arc<T>> {
+    slot.lock().unwrap_or_else(PoisonError::into_inner).clone()
 }
 
 /// Set a password callback for GUI applications
@@ -63,6 +103,17 @@ thread_local! {
 /// 
 /// Pass `None` to restore the default console-based authentication.
 /// 
+/// # Thread safety
+/// 
+/// There is a single callback slot for the whole process. Setting a callback
+/// on any thread replaces the callback every other thread uses, and passing
+/// `None` on any thread removes it for all of them; prompts on other threads
+/// are then cancelled. CUPS itself keeps the C-level registration per thread,
+/// so a thread only consults the shared slot after it has called this
+/// function (or [`set_credentials`]) at least once. Credentials set with
+/// [`set_credentials`] belong to their own thread and take precedence over
+/// the shared callback there.
+/// 
 /// # Arguments
 /// - `callback`: The password callback function, or None to restore default
 /// 
@@ -80,18 +131,62 @@ thread_local! {
 pub fn set_password_callback(callback: Option<Box<PasswordCallback>>) -> Result<()> {
     let has_callback = callback.is_some();
     
-    PASSWORD_CALLBACK.with(|cb| {
-        *cb.borrow_mut() = callback.map(|c| Arc::from(c));
+    store_callback(&PASSWORD_CALLBACK, callback.map(Arc::from));
+    register_password_wrapper(has_callback || has_credentials());
+
+    Ok(())
+}
+
+/// Authenticate with a fixed username and password
+/// 
+/// Sets the CUPS user for the calling thread and answers the first password
+/// prompt on that thread with `password`. Later prompts are cancelled, so a
+/// wrong password fails instead of being retried forever. Call this again
+/// before each operation that needs a fresh attempt.
+/// 
+/// # Thread safety
+/// 
+/// Like the CUPS user, the credentials only apply to the calling thread:
+/// prompts on other threads never receive this password, and setting or
+/// clearing credentials leaves other threads' credentials alone. On this
+/// thread they take precedence over the shared [`set_password_callback`]
+/// callback, which is left in place for other threads.
+/// 
+/// # Example
+/// ```rust
+/// use cups_rs::auth::{clear_credentials, set_credentials};
+/// 
+/// let result = set_credentials("print-service", "secret");
+/// assert!(result.is_ok());
+/// 
+/// let result = clear_credentials();
+/// assert!(result.is_ok());
+/// ```
+pub fn set_credentials(user: &str, password: &str) -> Result<()> {
+    // Reject embedded NUL bytes now rather than when CUPS asks for the password
+    CString::new(password)?;
+    set_user(Some(user))?;
+
+    CREDENTIALS.with(|credentials| {
+        *credentials.borrow_mut() = Some(Credentials {
+            password: password.to_string(),
+            answered: false,
+        });
     });
+    register_password_wrapper(true);
 
-    // Set the C callback function
-    unsafe {
-        if has_callback {
-            bindings::cupsSetPasswordCB2(Some(password_callback_wrapper), ptr::null_mut());
-        } else {
-            bindings::cupsSetPasswordCB2(None, ptr::null_mut());
-        }
-    }
+    Ok(())
+}
+
+/// Remove credentials set with [`set_credentials`] on the calling thread
+/// 
+/// Restores the default CUPS user for this thread. Prompts go back to the
+/// shared [`set_password_callback`] callback if one is set, or to the default
+/// console-based authentication otherwise.
+pub fn clear_credentials() -> Result<()> {
+    set_user(None)?;
+    CREDENTIALS.with(|credentials| credentials.borrow_mut().take());
+    register_password_wrapper(load_callback(&PASSWORD_CALLBACK).is_some());
 
     Ok(())
 }
@@ -118,9 +213,7 @@ pub fn set_password_callback(callback: Option<Box<PasswordCallback>>) -> Result<
 /// assert!(result.is_ok());
 /// ```
 pub fn set_client_cert_callback(callback: Option<Box<ClientCertCallback>>) -> Result<()> {
-    CLIENT_CERT_CALLBACK.with(|cb| {
-        *cb.borrow_mut() = callback.map(|c| Arc::from(c));
-    });
+    store_callback(&CLIENT_CERT_CALLBACK, callback.map(Arc::from));
 
     // Note: cupsSetClientCertCB might not be available in all CUPS versions
     // This is a placeholder for when the binding is available
@@ -151,9 +244,7 @@ pub fn set_client_cert_callback(callback: Option<Box<ClientCertCallback>>) -> Re
 /// assert!(result.is_ok());
 /// ```
 pub fn set_server_cert_callback(callback: Option<Box<ServerCertCallback>>) -> Result<()> {
-    SERVER_CERT_CALLBACK.with(|cb| {
-        *cb.borrow_mut() = callback.map(|c| Arc::from(c));
-    });
+    store_callback(&SERVER_CERT_CALLBACK, callback.map(Arc::from));
 
     // Note: cupsSetServerCertCB might not be available in all CUPS versions
     // This is a placeholder for when the binding is available
@@ -164,11 +255,12 @@ pub fn set_server_cert_callback(callback: Option<Box<ServerCertCallback>>) -> Re
 /// Get a password using the current password callback
 /// 
 /// This function calls the current password callback to get a password
-/// for authentication. It's typically used internally by CUPS.
+/// for authentication. It's typically used internally by CUPS. Credentials
+/// set with [`set_credentials`] on the calling thread are used first.
 /// 
 /// # Arguments
 /// - `prompt`: The authentication prompt
-/// - `http`: Optional HTTP connection
+/// - `server`: Hostname of the server requesting credentials, if known
 /// - `method`: HTTP method being used
 /// - `resource`: The resource being accessed
 /// 
@@ -177,18 +269,26 @@ pub fn set_server_cert_callback(callback: Option<Box<ServerCertCallback>>) -> Re
 /// - `None`: No password callback set or user cancelled
 pub fn get_password(
     prompt: &str,
-    http: Option<&str>,
+    server: Option<&str>,
     method: &str,
     resource: &str,
 ) -> Option<String> {
-    PASSWORD_CALLBACK.with(|cb| {
-        let callback_ref = cb.borrow();
-        if let Some(callback) = callback_ref.as_ref() {
-            callback(prompt, http, method, resource)
-        } else {
-            None
-        }
-    })
+    let credentials = CREDENTIALS.with(|credentials| {
+        credentials.borrow_mut().as_mut().map(|credentials| {
+            if credentials.answered {
+                None
+            } else {
+                credentials.answered = true;
+                Some(credentials.password.clone())
+            }
+        })
+    });
+    if let Some(password) = credentials {
+        return password;
+    }
+
+    load_callback(&PASSWORD_CALLBACK)
+        .and_then(|callback| callback(prompt, server, method, resource))
 }
 
 /// Get a client certificate using the current callback
@@ -203,14 +303,7 @@ pub fn get_password(
 /// - `Some(Vec<u8>)`: The certificate data in DER format
 /// - `None`: No certificate callback set or no certificate available
 pub fn get_client_certificate(server_name: &str) -> Option<Vec<u8>> {
-    CLIENT_CERT_CALLBACK.with(|cb| {
-        let callback_ref = cb.borrow();
-        if let Some(callback) = callback_ref.as_ref() {
-            callback(server_name)
-        } else {
-            None
-        }
-    })
+    load_callback(&CLIENT_CERT_CALLBACK).and_then(|callback| callback(server_name))
 }
 
 /// Validate a server certificate using the current callback
@@ -226,14 +319,10 @@ pub fn get_client_certificate(server_name: &str) -> Option<Vec<u8>> {
 /// - `true`: Certificate is valid/accepted
 /// - `false`: Certificate is invalid/rejected or no callback set
 pub fn validate_server_certificate(server_name: &str, certificate: &[u8]) -> bool {
-    SERVER_CERT_CALLBACK.with(|cb| {
-        let callback_ref = cb.borrow();
-        if let Some(callback) = callback_ref.as_ref() {
-            callback(server_name, certificate)
-        } else {
-            false // Default to reject if no callback
-        }
-    })
+    match load_callback(&SERVER_CERT_CALLBACK) {
+        Some(callback) => callback(server_name, certificate),
+        None => false, // Default to reject if no callback
+    }
 }
 
 /// Perform authentication for an HTTP request
@@ -278,11 +367,14 @@ pub fn do_authentication(
 /// Internal C callback wrapper for password callbacks
 extern "C" fn password_callback_wrapper(
     prompt: *const c_char,
-    _http: *mut bindings::_http_s,
+    http: *mut bindings::_http_s,
     method: *const c_char,
     resource: *const c_char,
     _user_data: *mut std::os::raw::c_void,
 ) -> *const c_char {
+    // CUPS is done with the previous password once it asks again
+    LAST_PASSWORD.with(|last| last.borrow_mut().take());
+
     // Safety: We ensure these pointers are valid C strings from CUPS
     let prompt_str = if prompt.is_null() {
         ""
@@ -302,24 +394,17 @@ extern "C" fn password_callback_wrapper(
         unsafe { CStr::from_ptr(resource).to_str().unwrap_or("/") }
     };
 
+    let server = http_hostname(http);
+
     // Get password from Rust callback
-    let password = PASSWORD_CALLBACK.with(|cb| {
-        let callback_ref = cb.borrow();
-        if let Some(callback) = callback_ref.as_ref() {
-            callback(prompt_str, None, method_str, resource_str)
-        } else {
-            None
-        }
-    });
+    let password = get_password(prompt_str, server.as_deref(), method_str, resource_str);
 
     match password {
         Some(pwd) => {
-            // Convert to C string and return
-            // Note: This creates a memory leak, but CUPS expects the string to remain valid
-            // until the next authentication call. This is how the CUPS API works.
-            let c_string = CString::new(pwd).unwrap_or_else(|_| CString::new("").unwrap());
-            let ptr = c_string.into_raw();
-            ptr
+            // CUPS expects the string to remain valid until the next authentication
+            // call, so it is owned by LAST_PASSWORD until then
+            let c_string = CString::new(pwd).unwrap_or_default();
+            LAST_PASSWORD.with(|last| last.borrow_mut().insert(c_string).as_ptr())
         }
         None => ptr::null(),
     }
@@ -328,8 +413,10 @@ extern "C" fn password_callback_wrapper(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serial_test::serial;
 
     #[test]
+    #[serial]
     fn test_set_password_callback() {
         let result = set_password_callback(Some(Box::new(|_prompt, _http, _method, _resource| {
             Some("test_password".to_string())
@@ -349,6 +436,116 @@ mod tests {
     }
 
     #[test]
+    #[serial]
+    fn test_password_callback_visible_from_other_threads() {
+        set_password_callback(Some(Box::new(|_prompt, _http, _method, _resource| {
+            Some("shared".to_string())
+        })))
+        .unwrap();
+
+        let password = std::thread::spawn(|| get_password("Password:", None, "GET", "/"))
+            .join()
+            .unwrap();
+        assert_eq!(password, Some("shared".to_string()));
+
+        set_password_callback(None).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_password_callback_wrapper_reuses_storage() {
+        set_password_callback(Some(Box::new(|prompt, _http, _method, _resource| {
+            Some(format!("{}-secret", prompt))
+        })))
+        .unwrap();
+
+        let prompt = CString::new("first").unwrap();
+        let first = password_callback_wrapper(
+            prompt.as_ptr(),
+            ptr::null_mut(),
+            ptr::null(),
+            ptr::null(),
+            ptr::null_mut(),
+        );
+        assert_eq!(unsafe { CStr::from_ptr(first) }.to_str().unwrap(), "first-secret");
+
+        let prompt = CString::new("second").unwrap();
+        let second = password_callback_wrapper(
+            prompt.as_ptr(),
+            ptr::null_mut(),
+            ptr::null(),
+            ptr::null(),
+            ptr::null_mut(),
+        );
+        assert_eq!(unsafe { CStr::from_ptr(second) }.to_str().unwrap(), "second-secret");
+
+        set_password_callback(None).unwrap();
+        let cancelled = password_callback_wrapper(
+            prompt.as_ptr(),
+            ptr::null_mut(),
+            ptr::null(),
+            ptr::null(),
+            ptr::null_mut(),
+        );
+        assert!(cancelled.is_null());
+        assert!(LAST_PASSWORD.with(|last| last.borrow().is_none()));
+    }
+
+    #[test]
+    #[serial]
+    fn test_set_credentials() {
+        set_credentials("print-service", "secret").unwrap();
+
+        assert_eq!(
+            get_password("Password:", None, "POST", "/"),
+            Some("secret".to_string())
+        );
+        // A second prompt means the password was rejected
+        assert_eq!(get_password("Password:", None, "POST", "/"), None);
+
+        clear_credentials().unwrap();
+        assert_eq!(get_password("Password:", None, "POST", "/"), None);
+
+        assert!(set_credentials("print-service", "bad\0password").is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_credentials_stay_on_their_thread() {
+        set_password_callback(Some(Box::new(|_prompt, _http, _method, _resource| {
+            Some("shared".to_string())
+        })))
+        .unwrap();
+        set_credentials("print-service", "secret").unwrap();
+
+        // Another thread gets the shared callback, not this thread's password
+        let other = std::thread::spawn(|| {
+            let first = get_password("Password:", None, "POST", "/");
+            clear_credentials().unwrap();
+            (first, get_password("Password:", None, "POST", "/"))
+        })
+        .join()
+        .unwrap();
+        assert_eq!(other.0, Some("shared".to_string()));
+        assert_eq!(other.1, Some("shared".to_string()));
+
+        // Its prompt and clear_credentials left this thread's answer in place
+        assert_eq!(
+            get_password("Password:", None, "POST", "/"),
+            Some("secret".to_string())
+        );
+        assert_eq!(get_password("Password:", None, "POST", "/"), None);
+
+        clear_credentials().unwrap();
+        assert_eq!(
+            get_password("Password:", None, "POST", "/"),
+            Some("shared".to_string())
+        );
+        set_password_callback(None).unwrap();
+    }
+
+    #[test]
+    #[serial]
     fn test_certificate_callbacks() {
         // Test client certificate callback
         let cert_data = vec![1, 2, 3, 4, 5];
@@ -5,6 +5,7 @@ use std::ptr;
 
 /// Encryption modes for CUPS connections
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EncryptionMode {
     /// Never use encryption
     Never,
@@ -353,6 +354,7 @@ impl Drop for CupsConfig {
 
 /// Summary of current CUPS configuration
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ConfigSummary {
     pub server: String,
     pub user: String,
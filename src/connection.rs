@@ -1,9 +1,12 @@
 use crate::bindings;
+use crate::config::EncryptionMode;
 use crate::destination::{DestCallback, Destination};
 use crate::error::{Error, Result};
-use std::marker::PhantomData;
-use std::os::raw::{c_int, c_void};
+use std::cell::{Cell, RefCell};
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int, c_void};
 use std::ptr;
+use std::rc::{Rc, Weak};
 use std::sync::atomic::{AtomicBool, Ordering};
 
 /// Connection flags for controlling how to connect to a destination
@@ -29,6 +32,15 @@ impl From<ConnectionFlags> for u32 {
 /// This structure provides a safe wrapper around the CUPS `http_t` type.
 /// Connections are automatically closed when the HttpConnection is dropped.
 ///
+/// # Sharing and threads
+///
+/// Cloning an `HttpConnection` is cheap: every clone refers to the same
+/// underlying `http_t`, which is closed once the last clone is closed or
+/// dropped. Settings such as [`HttpConnection::set_timeout`] apply to all
+/// clones. CUPS `http_t` is not thread-safe, so `HttpConnection` is neither
+/// `Send` nor `Sync`; share it between tasks on one thread, and open a
+/// separate connection in each thread that needs one.
+///
 /// # Examples
 ///
 /// ```no_run
@@ -40,12 +52,55 @@ impl From<ConnectionFlags> for u32 {
 ///
 /// println!("Connected to: {}", connection.resource_path());
 /// ```
+#[derive(Clone)]
 pub struct HttpConnection {
+    /// The shared `http_t`, or `None` once this handle has been closed
+    shared: Option<Rc<SharedHttp>>,
+    resource: String,
+}
+
+/// An open `http_t`, closed when the last `HttpConnection` referring to it goes away
+struct SharedHttp {
     http: *mut bindings::_http_s,
+    /// Callback run by `httpSetTimeout`, which is handed this struct as user data
+    timeout_callback: RefCell<Option<TimeoutCallback>>,
+    /// Set while the timeout callback runs, so it cannot replace itself
+    in_timeout_callback: Cell<bool>,
+}
+
+/// A non-owning handle to an [`HttpConnection`]
+///
+/// Unlike a clone, it doesn't keep the underlying `http_t` open. Capture one
+/// in a [`HttpConnection::set_timeout_with_callback`] callback that needs the
+/// connection, instead of a clone that would keep the connection alive forever.
+#[derive(Clone)]
+pub struct WeakHttpConnection {
+    shared: Weak<SharedHttp>,
     resource: String,
-    _phantom: PhantomData<bindings::_http_s>,
 }
 
+impl WeakHttpConnection {
+    /// Get a connection handle back, or `None` once the connection has been
+    /// closed by every handle
+    pub fn upgrade(&self) -> Option<HttpConnection> {
+        self.shared.upgrade().map(|shared| HttpConnection {
+            shared: Some(shared),
+            resource: self.resource.clone(),
+        })
+    }
+}
+
+impl Drop for SharedHttp {
+    fn drop(&mut self) {
+        unsafe {
+            bindings::httpClose(self.http);
+        }
+    }
+}
+
+/// Called when a read or write times out; return true to keep waiting
+type TimeoutCallback = Box<dyn FnMut() -> bool>;
+
 impl HttpConnection {
     /// Create a new HttpConnection from a raw http_t pointer
     pub(crate) unsafe fn from_raw(http: *mut bindings::_http_s, resource: String) -> Result<Self> {
@@ -56,15 +111,99 @@ impl HttpConnection {
         }
 
         Ok(HttpConnection {
-            http,
+            shared: Some(Rc::new(SharedHttp {
+                http,
+                timeout_callback: RefCell::new(None),
+                in_timeout_callback: Cell::new(false),
+            })),
             resource,
-            _phantom: PhantomData,
         })
     }
 
+    /// Connect directly to a server or printer by URI
+    ///
+    /// Accepts URIs such as `ipp://host:631/printers/name`, e.g. from network
+    /// discovery, so printers that are not CUPS destinations can be reached.
+    /// The URI path becomes the connection's resource path. `ipps://` and
+    /// `https://` URIs always use encryption, whatever `encryption` says.
+    ///
+    /// # Arguments
+    /// * `uri` - The URI to connect to
+    /// * `encryption` - Encryption to use for the connection
+    /// * `timeout_ms` - Connection timeout in milliseconds, None for indefinite
+    pub fn connect_uri(
+        uri: &str,
+        encryption: EncryptionMode,
+        timeout_ms: Option<i32>,
+    ) -> Result<HttpConnection> {
+        let uri_c = CString::new(uri)?;
+
+        let mut scheme = [0 as c_char; 32];
+        let mut username = [0 as c_char; 256];
+        let mut host = [0 as c_char; 256];
+        let mut resource = [0 as c_char; 1024];
+        let mut port: c_int = 0;
+
+        let status = unsafe {
+            bindings::httpSeparateURI(
+                bindings::http_uri_coding_e_HTTP_URI_CODING_ALL,
+                uri_c.as_ptr(),
+                scheme.as_mut_ptr(),
+                scheme.len() as c_int,
+                username.as_mut_ptr(),
+                username.len() as c_int,
+                host.as_mut_ptr(),
+                host.len() as c_int,
+                &mut port,
+                resource.as_mut_ptr(),
+                resource.len() as c_int,
+            )
+        };
+
+        let scheme = unsafe { CStr::from_ptr(scheme.as_ptr()) }.to_string_lossy().into_owned();
+        let resource = unsafe { CStr::from_ptr(resource.as_ptr()) }
+            .to_string_lossy()
+            .into_owned();
+
+        if status < bindings::http_uri_status_e_HTTP_URI_STATUS_OK || host[0] == 0 {
+            return Err(Error::ConnectionFailed(format!("Invalid URI '{}'", uri)));
+        }
+
+        let encryption = if scheme == "ipps" || scheme == "https" {
+            EncryptionMode::Always
+        } else {
+            encryption
+        };
+
+        let http = unsafe {
+            bindings::httpConnect2(
+                host.as_ptr(),
+                port,
+                ptr::null_mut(),
+                libc::AF_UNSPEC,
+                encryption.into(),
+                1,
+                timeout_ms.unwrap_or(-1),
+                ptr::null_mut(),
+            )
+        };
+
+        if http.is_null() {
+            return Err(Error::ConnectionFailed(format!(
+                "Failed to connect to '{}'",
+                uri
+            )));
+        }
+
+        unsafe { HttpConnection::from_raw(http, resource) }
+    }
+
     /// Get the raw pointer to the http_t structure
     pub fn as_ptr(&self) -> *mut bindings::_http_s {
-        self.http
+        match &self.shared {
+            Some(shared) => shared.http,
+            None => ptr::null_mut(),
+        }
     }
 
     /// Get the resource path for this connection
@@ -72,26 +211,256 @@ impl HttpConnection {
         &self.resource
     }
 
+    /// Get a handle that refers to this connection without keeping it open
+    pub fn downgrade(&self) -> WeakHttpConnection {
+        WeakHttpConnection {
+            shared: self.shared.as_ref().map(Rc::downgrade).unwrap_or_default(),
+            resource: self.resource.clone(),
+        }
+    }
+
     /// Close the HTTP connection
+    ///
+    /// If other clones of this connection are still open, only this handle is
+    /// closed; the underlying connection stays open until the last one closes.
     pub fn close(&mut self) {
-        if !self.http.is_null() {
-            unsafe {
-                bindings::httpClose(self.http);
-            }
-            self.http = ptr::null_mut();
+        self.shared = None;
+    }
+
+    /// Re-establish the connection to the same server and resource
+    ///
+    /// Recovers from a dropped socket, e.g. after the server restarted, while
+    /// keeping the encryption and authentication settings. A connection
+    /// closed with [`HttpConnection::close`] has been freed and cannot be
+    /// reconnected.
+    ///
+    /// # Arguments
+    /// * `timeout_ms` - Connection timeout in milliseconds, None for indefinite
+    pub fn reconnect(&mut self, timeout_ms: Option<i32>) -> Result<()> {
+        let http = self.checked_ptr()?;
+
+        let status =
+            unsafe { bindings::httpReconnect2(http, timeout_ms.unwrap_or(-1), ptr::null_mut()) };
+
+        if status == 0 {
+            Ok(())
+        } else {
+            Err(Error::ConnectionFailed(format!(
+                "Failed to reconnect to '{}'",
+                http_hostname(http).unwrap_or_else(|| "server".to_string())
+            )))
         }
     }
 
+    /// Set the timeout for reads and writes on this connection
+    ///
+    /// Once connected, CUPS waits indefinitely for slow servers by default; a
+    /// timeout makes a stalled request fail instead. Requests that time out
+    /// fail with an error.
+    ///
+    /// # Arguments
+    /// * `seconds` - Time to wait for data before giving up, must be positive
+    pub fn set_timeout(&mut self, seconds: f64) -> Result<()> {
+        self.register_timeout(seconds, None)
+    }
+
+    /// Set the read/write timeout with a callback deciding whether to keep waiting
+    ///
+    /// `callback` is called each time `seconds` pass without data; returning
+    /// `true` keeps waiting and `false` aborts the request. This keeps an idle
+    /// monitor connection open on a slow link while still letting the caller
+    /// give up, e.g. on shutdown.
+    ///
+    /// The callback is stored with the connection, so capturing a clone of
+    /// this connection in it creates a reference cycle and the connection is
+    /// never closed. Capture [`HttpConnection::downgrade`] instead. The
+    /// callback cannot change the timeout itself: `set_timeout` and
+    /// `set_timeout_with_callback` fail with `Error::ConfigurationError` while
+    /// it runs.
+    pub fn set_timeout_with_callback<F>(&mut self, seconds: f64, callback: F) -> Result<()>
+    where
+        F: FnMut() -> bool + 'static,
+    {
+        self.register_timeout(seconds, Some(Box::new(callback)))
+    }
+
+    fn register_timeout(&mut self, seconds: f64, callback: Option<TimeoutCallback>) -> Result<()> {
+        let http = self.checked_ptr()?;
+        if !(seconds.is_finite() && seconds > 0.0) {
+            return Err(Error::ConfigurationError(format!(
+                "Invalid connection timeout {}",
+                seconds
+            )));
+        }
+
+        let Some(shared) = &self.shared else {
+            return Err(Error::ConnectionFailed("Connection is closed".to_string()));
+        };
+        if shared.in_timeout_callback.get() {
+            return Err(Error::ConfigurationError(
+                "The timeout cannot be changed from inside the timeout callback".to_string(),
+            ));
+        }
+
+        let (cb, user_data): (bindings::http_timeout_cb_t, *mut c_void) = if callback.is_some() {
+            (Some(timeout_callback), Rc::as_ptr(shared) as *mut c_void)
+        } else {
+            (None, ptr::null_mut())
+        };
+
+        *shared.timeout_callback.borrow_mut() = callback;
+        unsafe { bindings::httpSetTimeout(http, seconds, cb, user_data) };
+        Ok(())
+    }
+
     /// Check if the connection is still valid
     pub fn is_connected(&self) -> bool {
-        !self.http.is_null()
+        self.shared.is_some()
+    }
+
+    /// Get the encryption mode requested for this connection
+    ///
+    /// This is the policy, not whether TLS is active; use
+    /// [`HttpConnection::is_encrypted`] to confirm the session is protected.
+    /// A closed connection reports `EncryptionMode::Never`.
+    pub fn encryption(&self) -> EncryptionMode {
+        let http = self.as_ptr();
+        if http.is_null() {
+            return EncryptionMode::Never;
+        }
+
+        unsafe { bindings::httpGetEncryption(http) }.into()
+    }
+
+    /// Check if a TLS session is currently established
+    pub fn is_encrypted(&self) -> bool {
+        let http = self.as_ptr();
+        !http.is_null() && unsafe { bindings::httpIsEncrypted(http) } != 0
+    }
+
+    /// Get the address of the server at the other end of the connection
+    ///
+    /// Returns the numeric address when known, otherwise the hostname used to
+    /// connect. Returns `None` for a closed connection.
+    pub fn peer_address(&self) -> Option<String> {
+        let http = self.as_ptr();
+        if http.is_null() {
+            return None;
+        }
+
+        let addr = unsafe { bindings::httpGetAddress(http) };
+        if !addr.is_null() {
+            let mut buffer = [0 as c_char; 256];
+            let address =
+                unsafe { bindings::httpAddrString(addr, buffer.as_mut_ptr(), buffer.len() as c_int) };
+            if !address.is_null() {
+                let address = unsafe { CStr::from_ptr(address) }.to_string_lossy().into_owned();
+                if !address.is_empty() {
+                    return Some(address);
+                }
+            }
+        }
+
+        http_hostname(http)
+    }
+
+    /// Get the raw pointer, failing if the connection has been closed
+    ///
+    /// A null `http_t` means "use the default server" to CUPS, so passing a
+    /// closed connection through unchecked would silently talk to the wrong server.
+    pub(crate) fn checked_ptr(&self) -> Result<*mut bindings::_http_s> {
+        let http = self.as_ptr();
+        if http.is_null() {
+            Err(Error::ConnectionFailed("Connection is closed".to_string()))
+        } else {
+            Ok(http)
+        }
     }
 }
 
-impl Drop for HttpConnection {
-    fn drop(&mut self) {
-        self.close();
+/// Get the hostname of the server an `http_t` is connected to
+pub(crate) fn http_hostname(http: *mut bindings::_http_s) -> Option<String> {
+    if http.is_null() {
+        return None;
+    }
+
+    let mut buffer = [0 as c_char; 256];
+    let hostname =
+        unsafe { bindings::httpGetHostname(http, buffer.as_mut_ptr(), buffer.len() as c_int) };
+
+    if hostname.is_null() {
+        None
+    } else {
+        let hostname = unsafe { CStr::from_ptr(hostname) }.to_string_lossy().into_owned();
+        Some(hostname).filter(|h| !h.is_empty())
+    }
+}
+
+/// Build a URI with `httpAssembleURI`, percent-escaping each component
+///
+/// Matches `httpAssembleURIf(HTTP_URI_CODING_ALL, ...)` as used by the CUPS
+/// command-line tools, so queue names containing `%`, spaces or non-ASCII
+/// characters produce valid URIs. A `port` of 0 leaves the port out.
+pub(crate) fn assemble_uri(scheme: &str, host: &str, port: c_int, resource: &str) -> Result<String> {
+    let scheme_c = CString::new(scheme)?;
+    let host_c = CString::new(host)?;
+    let resource_c = CString::new(resource)?;
+
+    let mut uri = [0 as c_char; 1024];
+    let status = unsafe {
+        bindings::httpAssembleURI(
+            bindings::http_uri_coding_e_HTTP_URI_CODING_ALL,
+            uri.as_mut_ptr(),
+            uri.len() as c_int,
+            scheme_c.as_ptr(),
+            ptr::null(),
+            host_c.as_ptr(),
+            port,
+            resource_c.as_ptr(),
+        )
+    };
+
+    if status < bindings::http_uri_status_e_HTTP_URI_STATUS_OK {
+        return Err(Error::ConfigurationError(format!(
+            "Cannot build a URI for '{}' on '{}'",
+            resource, host
+        )));
     }
+
+    Ok(unsafe { CStr::from_ptr(uri.as_ptr()) }.to_string_lossy().into_owned())
+}
+
+/// Get the percent-decoded resource path of a URI, e.g. `/printers/Büro`
+pub(crate) fn uri_resource(uri: &str) -> Option<String> {
+    let uri_c = CString::new(uri).ok()?;
+
+    let mut scheme = [0 as c_char; 32];
+    let mut username = [0 as c_char; 256];
+    let mut host = [0 as c_char; 256];
+    let mut resource = [0 as c_char; 1024];
+    let mut port: c_int = 0;
+
+    let status = unsafe {
+        bindings::httpSeparateURI(
+            bindings::http_uri_coding_e_HTTP_URI_CODING_ALL,
+            uri_c.as_ptr(),
+            scheme.as_mut_ptr(),
+            scheme.len() as c_int,
+            username.as_mut_ptr(),
+            username.len() as c_int,
+            host.as_mut_ptr(),
+            host.len() as c_int,
+            &mut port,
+            resource.as_mut_ptr(),
+            resource.len() as c_int,
+        )
+    };
+
+    if status < bindings::http_uri_status_e_HTTP_URI_STATUS_OK {
+        return None;
+    }
+
+    Some(unsafe { CStr::from_ptr(resource.as_ptr()) }.to_string_lossy().into_owned())
 }
 
 impl Destination {
@@ -115,7 +484,8 @@ impl Destination {
         cancel: Option<&AtomicBool>,
     ) -> Result<HttpConnection> {
         // Create a raw cups_dest_t for this destination
-        let dest_ptr = self.as_ptr();
+        let raw_dest = self.as_ptr();
+        let dest_ptr = raw_dest.as_ptr();
         if dest_ptr.is_null() {
             return Err(Error::NullPointer);
         }
@@ -166,6 +536,48 @@ impl Destination {
         unsafe { HttpConnection::from_raw(http_conn, resource) }
     }
 
+    /// Connect to this destination with an explicit encryption mode
+    ///
+    /// Unlike [`Destination::connect`], which follows the thread-wide setting
+    /// from `config::set_encryption`, the mode is applied to this connection
+    /// only. With `Required` or `Always` the connection is upgraded to TLS and
+    /// an error is returned if that fails.
+    ///
+    /// With `ConnectionFlags::Device` and `Required` or `Always`, the printer's
+    /// [`Destination::secure_uri`] is used when it advertises one; `cancel` is
+    /// not checked in that case. Devices without a secure URI whose URI does
+    /// not support TLS (e.g. `socket://`) may not honor the requested encryption.
+    pub fn connect_with_encryption(
+        &self,
+        flags: ConnectionFlags,
+        timeout_ms: Option<i32>,
+        cancel: Option<&AtomicBool>,
+        encryption: EncryptionMode,
+    ) -> Result<HttpConnection> {
+        let wants_tls = matches!(encryption, EncryptionMode::Required | EncryptionMode::Always);
+        let secure_uri = if flags == ConnectionFlags::Device && wants_tls {
+            self.secure_uri()
+        } else {
+            None
+        };
+
+        if let Some(uri) = secure_uri {
+            return HttpConnection::connect_uri(&uri, encryption, timeout_ms);
+        }
+
+        let connection = self.connect(flags, timeout_ms, cancel)?;
+
+        let status = unsafe { bindings::httpEncryption(connection.as_ptr(), encryption.into()) };
+        if status != 0 {
+            return Err(Error::ConnectionFailed(format!(
+                "Failed to set {:?} encryption for destination '{}'",
+                encryption, self.name
+            )));
+        }
+
+        Ok(connection)
+    }
+
     /// Connect to this destination with a callback
     /// 
     /// Opens a connection with a callback function that can monitor the
@@ -190,7 +602,8 @@ impl Destination {
         user_data: &mut T,
     ) -> Result<HttpConnection> {
         // Create a raw cups_dest_t for this destination
-        let dest_ptr = self.as_ptr();
+        let raw_dest = self.as_ptr();
+        let dest_ptr = raw_dest.as_ptr();
         if dest_ptr.is_null() {
             return Err(Error::NullPointer);
         }
@@ -248,6 +661,27 @@ impl Destination {
     }
 }
 
+// C-compatible callback for connection timeouts
+//
+// `user_data` is the connection's `SharedHttp`, which outlives the `http_t`
+// CUPS calls this for. Replacing the callback is refused while it runs, so the
+// borrow below is never contended and the running closure is never dropped.
+unsafe extern "C" fn timeout_callback(
+    _http: *mut bindings::_http_s,
+    user_data: *mut c_void,
+) -> c_int {
+    let shared = unsafe { &*(user_data as *const SharedHttp) };
+
+    shared.in_timeout_callback.set(true);
+    let keep_waiting = match shared.timeout_callback.borrow_mut().as_mut() {
+        Some(callback) => callback(),
+        None => false,
+    };
+    shared.in_timeout_callback.set(false);
+
+    if keep_waiting { 1 } else { 0 }
+}
+
 // Context structure for the connection callback
 struct ConnectContext<'a, T> {
     callback: &'a mut DestCallback<T>,
@@ -309,12 +743,192 @@ mod tests {
     use super::*;
     use crate::destination::get_all_destinations;
 
+    fn closed_connection() -> HttpConnection {
+        HttpConnection {
+            shared: None,
+            resource: String::new(),
+        }
+    }
+
+    fn has_timeout_callback(connection: &HttpConnection) -> bool {
+        connection
+            .shared
+            .as_ref()
+            .is_some_and(|shared| shared.timeout_callback.borrow().is_some())
+    }
+
     #[test]
     fn test_connection_flags() {
         assert_eq!(u32::from(ConnectionFlags::Scheduler), 0);
         assert_eq!(u32::from(ConnectionFlags::Device), 1);
     }
 
+    #[test]
+    fn test_http_hostname_without_connection() {
+        assert_eq!(http_hostname(ptr::null_mut()), None);
+    }
+
+    #[test]
+    fn test_connect_uri_rejects_invalid_uri() {
+        let result = HttpConnection::connect_uri("not a uri", EncryptionMode::IfRequested, Some(1000));
+        assert!(matches!(result, Err(Error::ConnectionFailed(_))));
+    }
+
+    #[test]
+    fn test_assemble_uri_escapes_resource() {
+        assert_eq!(
+            assemble_uri("ipp", "localhost", 0, "/printers/Office").unwrap(),
+            "ipp://localhost/printers/Office"
+        );
+        assert_eq!(
+            assemble_uri("ipp", "localhost", 0, "/printers/50%_Büro").unwrap(),
+            "ipp://localhost/printers/50%25_B%C3%BCro"
+        );
+        assert_eq!(
+            assemble_uri("ipps", "print.example.com", 443, "/jobs/7").unwrap(),
+            "ipps://print.example.com:443/jobs/7"
+        );
+    }
+
+    #[test]
+    fn test_uri_resource_decodes() {
+        let uri = assemble_uri("ipp", "localhost", 0, "/printers/50%_Büro").unwrap();
+        assert_eq!(uri_resource(&uri).as_deref(), Some("/printers/50%_Büro"));
+        assert_eq!(
+            uri_resource("ipp://office.local/ipp/print").as_deref(),
+            Some("/ipp/print")
+        );
+        assert_eq!(uri_resource("not a uri"), None);
+    }
+
+    #[test]
+    fn test_closed_connection_info() {
+        let connection = closed_connection();
+        assert_eq!(connection.encryption(), EncryptionMode::Never);
+        assert!(!connection.is_encrypted());
+        assert_eq!(connection.peer_address(), None);
+    }
+
+    #[test]
+    fn test_reconnect_closed_connection() {
+        let mut connection = closed_connection();
+        assert!(matches!(
+            connection.reconnect(Some(1000)),
+            Err(Error::ConnectionFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_set_timeout_closed_connection() {
+        let mut connection = closed_connection();
+        assert!(matches!(
+            connection.set_timeout(30.0),
+            Err(Error::ConnectionFailed(_))
+        ));
+        assert!(matches!(
+            connection.set_timeout_with_callback(30.0, || true),
+            Err(Error::ConnectionFailed(_))
+        ));
+        assert!(!has_timeout_callback(&connection));
+    }
+
+    /// An `http_t` that hasn't connected yet, so no server is needed
+    fn unconnected_connection() -> HttpConnection {
+        let host = CString::new("127.0.0.1").unwrap();
+        let http = unsafe {
+            bindings::httpConnect2(
+                host.as_ptr(),
+                1,
+                ptr::null_mut(),
+                libc::AF_UNSPEC,
+                EncryptionMode::Never.into(),
+                1,
+                0,
+                ptr::null_mut(),
+            )
+        };
+        unsafe { HttpConnection::from_raw(http, "/".to_string()) }.unwrap()
+    }
+
+    #[test]
+    fn test_timeout_callback_cannot_replace_itself() {
+        let mut conn = unconnected_connection();
+        let weak = conn.downgrade();
+        let replaced = Rc::new(Cell::new(None));
+
+        let result = Rc::clone(&replaced);
+        conn.set_timeout_with_callback(30.0, move || {
+            let mut conn = weak.upgrade().expect("connection is open");
+            result.set(Some(conn.set_timeout(10.0).is_ok()));
+            true
+        })
+        .unwrap();
+
+        let shared = Rc::as_ptr(conn.shared.as_ref().unwrap()) as *mut c_void;
+        assert_eq!(unsafe { timeout_callback(conn.as_ptr(), shared) }, 1);
+        assert_eq!(replaced.get(), Some(false));
+        assert!(has_timeout_callback(&conn));
+
+        // Outside the callback the timeout can be changed again
+        assert!(conn.set_timeout(10.0).is_ok());
+        assert!(!has_timeout_callback(&conn));
+    }
+
+    #[test]
+    fn test_weak_connection() {
+        let conn = unconnected_connection();
+        let weak = conn.downgrade();
+
+        let upgraded = weak.upgrade().unwrap();
+        assert_eq!(upgraded.as_ptr(), conn.as_ptr());
+        assert_eq!(upgraded.resource_path(), "/");
+
+        drop(upgraded);
+        drop(conn);
+        assert!(weak.upgrade().is_none());
+        assert!(closed_connection().downgrade().upgrade().is_none());
+    }
+
+    #[test]
+    fn test_set_timeout() {
+        // This test requires a CUPS server to be running
+        let Ok(destinations) = get_all_destinations() else { return };
+        let Some(dest) = destinations.first() else { return };
+        let Ok(mut conn) = dest.connect(ConnectionFlags::Scheduler, Some(1000), None) else {
+            return;
+        };
+
+        assert!(matches!(conn.set_timeout(0.0), Err(Error::ConfigurationError(_))));
+        assert!(conn.set_timeout(60.0).is_ok());
+        assert!(conn.set_timeout_with_callback(60.0, || true).is_ok());
+        assert!(has_timeout_callback(&conn));
+
+        // The callback belongs to the shared connection, not the handle
+        let clone = conn.clone();
+        assert!(has_timeout_callback(&clone));
+        assert!(conn.set_timeout(60.0).is_ok());
+        assert!(!has_timeout_callback(&clone));
+    }
+
+    #[test]
+    fn test_connection_clone() {
+        // This test requires a CUPS server to be running
+        let Ok(destinations) = get_all_destinations() else { return };
+        let Some(dest) = destinations.first() else { return };
+        let Ok(mut conn) = dest.connect(ConnectionFlags::Scheduler, Some(1000), None) else {
+            return;
+        };
+
+        let clone = conn.clone();
+        assert_eq!(clone.as_ptr(), conn.as_ptr());
+        assert_eq!(clone.resource_path(), conn.resource_path());
+
+        conn.close();
+        assert!(!conn.is_connected());
+        assert!(clone.is_connected());
+        assert!(clone.checked_ptr().is_ok());
+    }
+
     #[test]
     fn test_connect_to_scheduler() {
         // This test requires a CUPS server to be running
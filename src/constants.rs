@@ -7,25 +7,33 @@ pub const DEST_FLAGS_REMOVED: u32 = 1 << 1;
 pub const DEST_FLAGS_ERROR: u32 = 1 << 2;
 pub const DEST_FLAGS_DEVICE: u32 = 1 << 3;
 
-// Printer types
-pub const PRINTER_CLASS: u32 = 0x00000001;
-pub const PRINTER_FAX: u32 = 0x00000002;
+// Printer types (cups_ptype_t bits, as reported in printer-type)
 pub const PRINTER_LOCAL: u32 = 0;
-pub const PRINTER_REMOTE: u32 = 0x00000004;
-pub const PRINTER_DISCOVERED: u32 = 0x00000008;
-pub const PRINTER_BW: u32 = 0x00000010;
-pub const PRINTER_COLOR: u32 = 0x00000020;
-pub const PRINTER_DUPLEX: u32 = 0x00000040;
-pub const PRINTER_STAPLE: u32 = 0x00000080;
-pub const PRINTER_COLLATE: u32 = 0x00000100;
-pub const PRINTER_PUNCH: u32 = 0x00000200;
-pub const PRINTER_COVER: u32 = 0x00000400;
-pub const PRINTER_BIND: u32 = 0x00000800;
-pub const PRINTER_SORT: u32 = 0x00001000;
-pub const PRINTER_SMALL: u32 = 0x00002000;
-pub const PRINTER_MEDIUM: u32 = 0x00004000;
-pub const PRINTER_LARGE: u32 = 0x00008000;
-pub const PRINTER_VARIABLE: u32 = 0x00010000;
+pub const PRINTER_CLASS: u32 = 0x00000001;
+pub const PRINTER_REMOTE: u32 = 0x00000002;
+pub const PRINTER_BW: u32 = 0x00000004;
+pub const PRINTER_COLOR: u32 = 0x00000008;
+pub const PRINTER_DUPLEX: u32 = 0x00000010;
+pub const PRINTER_STAPLE: u32 = 0x00000020;
+pub const PRINTER_COPIES: u32 = 0x00000040;
+pub const PRINTER_COLLATE: u32 = 0x00000080;
+pub const PRINTER_PUNCH: u32 = 0x00000100;
+pub const PRINTER_COVER: u32 = 0x00000200;
+pub const PRINTER_BIND: u32 = 0x00000400;
+pub const PRINTER_SORT: u32 = 0x00000800;
+pub const PRINTER_SMALL: u32 = 0x00001000;
+pub const PRINTER_MEDIUM: u32 = 0x00002000;
+pub const PRINTER_LARGE: u32 = 0x00004000;
+pub const PRINTER_VARIABLE: u32 = 0x00008000;
+pub const PRINTER_DEFAULT: u32 = 0x00020000;
+pub const PRINTER_FAX: u32 = 0x00040000;
+pub const PRINTER_REJECTING: u32 = 0x00080000;
+pub const PRINTER_NOT_SHARED: u32 = 0x00200000;
+pub const PRINTER_AUTHENTICATED: u32 = 0x00400000;
+pub const PRINTER_COMMANDS: u32 = 0x00800000;
+pub const PRINTER_DISCOVERED: u32 = 0x01000000;
+pub const PRINTER_SCANNER: u32 = 0x02000000;
+pub const PRINTER_MFP: u32 = 0x04000000;
 
 // Media flags
 pub const MEDIA_FLAGS_DEFAULT: u32 = 0;
@@ -35,17 +43,41 @@ pub const MEDIA_FLAGS_EXACT: u32 = 1 << 2;
 pub const MEDIA_FLAGS_READY: u32 = 1 << 3;
 
 // Option names
+pub const COLUMNS: &str = "columns";
 pub const COPIES: &str = "copies";
+pub const CPI: &str = "cpi";
 pub const FINISHINGS: &str = "finishings";
+pub const JOB_HOLD_UNTIL: &str = "job-hold-until";
+pub const JOB_PASSWORD: &str = "job-password";
+pub const JOB_PASSWORD_ENCRYPTION: &str = "job-password-encryption";
+pub const JOB_PRIORITY: &str = "job-priority";
+pub const LPI: &str = "lpi";
 pub const MEDIA: &str = "media";
 pub const MEDIA_SOURCE: &str = "media-source";
 pub const MEDIA_TYPE: &str = "media-type";
+pub const MULTIPLE_DOCUMENT_HANDLING: &str = "multiple-document-handling";
 pub const NUMBER_UP: &str = "number-up";
+pub const NUMBER_UP_LAYOUT: &str = "number-up-layout";
 pub const ORIENTATION: &str = "orientation-requested";
+pub const PAGE_BORDER: &str = "page-border";
+pub const PAGE_DELIVERY: &str = "page-delivery";
+pub const PAGE_RANGES: &str = "page-ranges";
 pub const PRINT_COLOR_MODE: &str = "print-color-mode";
 pub const PRINT_QUALITY: &str = "print-quality";
+pub const PRINT_SCALING: &str = "print-scaling";
+pub const PRINTER_RESOLUTION: &str = "printer-resolution";
 pub const SIDES: &str = "sides";
 
+// Job hold values
+pub const JOB_HOLD_UNTIL_DAY_TIME: &str = "day-time";
+pub const JOB_HOLD_UNTIL_EVENING: &str = "evening";
+pub const JOB_HOLD_UNTIL_INDEFINITE: &str = "indefinite";
+pub const JOB_HOLD_UNTIL_NIGHT: &str = "night";
+pub const JOB_HOLD_UNTIL_NO_HOLD: &str = "no-hold";
+pub const JOB_HOLD_UNTIL_SECOND_SHIFT: &str = "second-shift";
+pub const JOB_HOLD_UNTIL_THIRD_SHIFT: &str = "third-shift";
+pub const JOB_HOLD_UNTIL_WEEKEND: &str = "weekend";
+
 // Media values
 pub const MEDIA_3X5: &str = "na_index-3x5_3x5in";
 pub const MEDIA_4X6: &str = "na_index-4x6_4x6in";
@@ -92,6 +124,13 @@ pub const PRINT_QUALITY_DRAFT: &str = "3";
 pub const PRINT_QUALITY_NORMAL: &str = "4";
 pub const PRINT_QUALITY_HIGH: &str = "5";
 
+// Print scaling values
+pub const PRINT_SCALING_AUTO: &str = "auto";
+pub const PRINT_SCALING_AUTO_FIT: &str = "auto-fit";
+pub const PRINT_SCALING_FILL: &str = "fill";
+pub const PRINT_SCALING_FIT: &str = "fit";
+pub const PRINT_SCALING_NONE: &str = "none";
+
 // Sides values
 pub const SIDES_ONE_SIDED: &str = "one-sided";
 pub const SIDES_TWO_SIDED_PORTRAIT: &str = "two-sided-long-edge";
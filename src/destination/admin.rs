@@ -0,0 +1,118 @@
+use super::Destination;
+use crate::config::get_user;
+use crate::connection::{ConnectionFlags, HttpConnection};
+use crate::error::{Error, Result};
+use crate::error_helpers::cups_error_to_our_error;
+use crate::ipp::{IppOperation, IppRequest, IppTag, IppValueTag};
+
+const CONNECT_TIMEOUT_MS: i32 = 30_000;
+
+/// Queue administration over IPP
+///
+/// These operations usually require the user to be a CUPS administrator;
+/// otherwise they fail with `Error::PermissionDenied` or
+/// `Error::AuthenticationRequired`.
+impl Destination {
+    /// Stop the printer from processing jobs, like `cupsdisable`
+    ///
+    /// Jobs can still be queued while the printer is paused.
+    pub fn pause(&self) -> Result<()> {
+        self.send_printer_operation(IppOperation::PausePrinter, "pause printer", None)
+    }
+
+    /// Resume processing jobs on a paused printer, like `cupsenable`
+    pub fn resume(&self) -> Result<()> {
+        self.send_printer_operation(IppOperation::ResumePrinter, "resume printer", None)
+    }
+
+    /// Allow or refuse new jobs, like `cupsaccept` and `cupsreject`
+    ///
+    /// Unlike [`Destination::pause`], this doesn't affect jobs already queued.
+    /// `reason` is shown to users as the `printer-state-message`, e.g.
+    /// "Out of service until Monday". Read the current setting with
+    /// [`Destination::is_accepting_jobs`] after a `refresh`.
+    pub fn set_accepting_jobs(&self, accept: bool, reason: Option<&str>) -> Result<()> {
+        if accept {
+            self.send_printer_operation(IppOperation::CupsAcceptJobs, "accept jobs", reason)
+        } else {
+            self.send_printer_operation(IppOperation::CupsRejectJobs, "reject jobs", reason)
+        }
+    }
+
+    /// Send an operation that targets the printer itself to the scheduler
+    fn send_printer_operation(
+        &self,
+        operation: IppOperation,
+        description: &str,
+        message: Option<&str>,
+    ) -> Result<()> {
+        let (mut request, connection) = self.new_request(operation)?;
+        request.add_string(
+            IppTag::Operation,
+            IppValueTag::Name,
+            "requesting-user-name",
+            &get_user(),
+        )?;
+        if let Some(message) = message {
+            request.add_string(
+                IppTag::Operation,
+                IppValueTag::Text,
+                "printer-state-message",
+                message,
+            )?;
+        }
+
+        // CUPS refuses /admin requests it doesn't allow (HTTP 403) without an
+        // IPP response, leaving the reason in cupsLastError
+        let response = match request.send(&connection, "/admin/") {
+            Ok(response) => response,
+            Err(Error::ServerError(_)) => {
+                return Err(cups_error_to_our_error(description, Some(&self.name)));
+            }
+            Err(e) => return Err(e),
+        };
+
+        if response.is_successful() {
+            Ok(())
+        } else {
+            Err(cups_error_to_our_error(description, Some(&self.name)))
+        }
+    }
+}
+
+/// Low-level IPP access
+impl Destination {
+    /// Start an IPP request that targets this destination
+    ///
+    /// Connects to the scheduler and creates a request for `operation` with the
+    /// `printer-uri` operation attribute already set to the first of
+    /// [`Destination::uris`].
+    /// Send it with `request.send(&connection, connection.resource_path())`.
+    pub fn new_request(&self, operation: IppOperation) -> Result<(IppRequest, HttpConnection)> {
+        let connection =
+            self.connect(ConnectionFlags::Scheduler, Some(CONNECT_TIMEOUT_MS), None)?;
+
+        let mut request = IppRequest::new(operation)?;
+        request.add_string(
+            IppTag::Operation,
+            IppValueTag::Uri,
+            "printer-uri",
+            &self.printer_uri()?,
+        )?;
+
+        Ok((request, connection))
+    }
+
+    /// The URI the scheduler knows this destination by
+    ///
+    /// Uses the first URI in `printer-uri-supported`, since printers often
+    /// advertise several, and falls back to an escaped
+    /// `ipp://localhost/printers/<name>` URI when the destination doesn't
+    /// report one.
+    pub(crate) fn printer_uri(&self) -> Result<String> {
+        match self.uris().into_iter().next() {
+            Some(uri) => Ok(uri),
+            None => crate::job::printer_uri(&self.name),
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use super::{Destination, DestinationInfo, MediaSize, PrinterDefaults};
+use crate::bindings;
+use crate::constants::{MEDIA_FLAGS_BORDERLESS, MEDIA_FLAGS_DEFAULT};
+use crate::error::{Error, Result};
+use crate::error_helpers::validate_document_format;
+use crate::job::{Finishing, Resolution};
+use std::collections::HashMap;
+use std::ptr;
+
+/// Capability queries that manage the CUPS pointers internally
+///
+/// Each call fetches the destination's detailed information from the default
+/// CUPS server and frees it afterwards. For many queries in a row, call
+/// [`Destination::get_detailed_info`] once and use the [`DestinationInfo`]
+/// methods directly.
+impl Destination {
+    /// Check if an option is supported
+    pub fn supports_option(&self, option: &str) -> Result<bool> {
+        self.with_info(|info, dest| Ok(info.is_option_supported(ptr::null_mut(), dest, option)))
+    }
+
+    /// Check if a specific option value is supported
+    pub fn supports_value(&self, option: &str, value: &str) -> Result<bool> {
+        self.with_info(|info, dest| Ok(info.is_value_supported(ptr::null_mut(), dest, option, value)))
+    }
+
+    /// Look up a media size by name, e.g. `MEDIA_A4`
+    pub fn media_by_name(&self, name: &str) -> Result<MediaSize> {
+        self.with_info(|info, dest| {
+            info.get_media_by_name(ptr::null_mut(), dest, name, MEDIA_FLAGS_DEFAULT)
+        })
+    }
+
+    /// Look up the borderless (zero-margin) version of a media size by name
+    ///
+    /// Fails with `Error::MediaSizeError` if the printer can't print the
+    /// media without borders.
+    pub fn borderless_media_by_name(&self, name: &str) -> Result<MediaSize> {
+        let media = self.with_info(|info, dest| {
+            info.get_media_by_name(ptr::null_mut(), dest, name, MEDIA_FLAGS_BORDERLESS)
+        })?;
+
+        if media.is_borderless() {
+            Ok(media)
+        } else {
+            Err(Error::MediaSizeError(format!(
+                "Media '{}' is not available borderless",
+                name
+            )))
+        }
+    }
+
+    /// Check if the printer can print the named media without borders
+    pub fn has_borderless(&self, name: &str) -> bool {
+        self.borderless_media_by_name(name).is_ok()
+    }
+
+    /// Look up a media size by dimensions in hundredths of millimeters
+    pub fn media_by_size(&self, width: i32, length: i32) -> Result<MediaSize> {
+        self.with_info(|info, dest| {
+            info.get_media_by_size(ptr::null_mut(), dest, width, length, MEDIA_FLAGS_DEFAULT)
+        })
+    }
+
+    /// Get the default media size
+    pub fn default_media(&self) -> Result<MediaSize> {
+        self.default_media_with_flags(MEDIA_FLAGS_DEFAULT)
+    }
+
+    /// Get the default media size matching `MEDIA_FLAGS_*` flags
+    pub fn default_media_with_flags(&self, flags: u32) -> Result<MediaSize> {
+        self.with_info(|info, dest| info.get_default_media(ptr::null_mut(), dest, flags))
+    }
+
+    /// Get all supported media sizes
+    pub fn all_media(&self) -> Result<Vec<MediaSize>> {
+        self.all_media_with_flags(MEDIA_FLAGS_DEFAULT)
+    }
+
+    /// Get all supported media sizes matching `MEDIA_FLAGS_*` flags
+    pub fn all_media_with_flags(&self, flags: u32) -> Result<Vec<MediaSize>> {
+        self.with_info(|info, dest| info.get_all_media(ptr::null_mut(), dest, flags))
+    }
+
+    /// Get the number of media sizes matching `MEDIA_FLAGS_*` flags
+    pub fn media_count(&self, flags: u32) -> Result<usize> {
+        self.with_info(|info, dest| {
+            Ok(info.get_media_count(ptr::null_mut(), dest, flags).max(0) as usize)
+        })
+    }
+
+    /// Get the media size at `index` among those matching `MEDIA_FLAGS_*` flags
+    ///
+    /// Indexes run from 0 to [`Destination::media_count`] with the same flags.
+    pub fn media_by_index(&self, index: usize, flags: u32) -> Result<MediaSize> {
+        let index = i32::try_from(index)
+            .map_err(|_| Error::MediaSizeError(format!("Media at index {} not found", index)))?;
+        self.with_info(|info, dest| info.get_media_by_index(ptr::null_mut(), dest, index, flags))
+    }
+
+    /// Get the media sizes currently loaded in the printer
+    pub fn ready_media(&self) -> Result<Vec<MediaSize>> {
+        self.with_info(|info, dest| info.get_ready_media(ptr::null_mut(), dest))
+    }
+
+    /// Get the finishing processes currently available
+    pub fn ready_finishings(&self) -> Result<Vec<Finishing>> {
+        self.with_info(|info, dest| info.get_ready_finishings(ptr::null_mut(), dest))
+    }
+
+    /// Get the printer's default value for an option
+    pub fn default_value(&self, option: &str) -> Result<Option<String>> {
+        self.with_info(|info, dest| info.get_default_value(ptr::null_mut(), dest, option))
+    }
+
+    /// Get the printer's default media, sides, color mode, quality,
+    /// resolution and copies in one call
+    ///
+    /// Defaults the printer doesn't report are left as `None`.
+    pub fn defaults(&self) -> Result<PrinterDefaults> {
+        self.with_info(|info, dest| {
+            let value = |option: &str| info.get_default_value(ptr::null_mut(), dest, option);
+
+            Ok(PrinterDefaults::from_values(
+                value("media")?,
+                value("sides")?,
+                value("print-color-mode")?,
+                value("print-quality")?,
+                value("copies")?,
+                info.get_default_resolution(ptr::null_mut(), dest)?,
+            ))
+        })
+    }
+
+    /// Get all supported values for an option
+    pub fn supported_values(&self, option: &str) -> Result<Vec<String>> {
+        self.with_info(|info, dest| info.get_supported_values(ptr::null_mut(), dest, option))
+    }
+
+    /// Check if the printer accepts documents of a MIME type, e.g. `FORMAT_PDF`
+    ///
+    /// Uses the same rules as job submission: the format must be listed in
+    /// `document-format-supported` (or be a common format if the printer
+    /// doesn't report the list), and raw and vendor formats are always accepted.
+    pub fn accepts_format(&self, mime: &str) -> Result<bool> {
+        let supported = self.supported_values("document-format")?;
+        Ok(validate_document_format(mime, &self.name, &supported).is_ok())
+    }
+
+    /// Get the range of copy counts the printer accepts, e.g. `(1, 999)`
+    pub fn copies_range(&self) -> Result<Option<(i32, i32)>> {
+        self.with_info(|info, dest| Ok(info.copies_range(ptr::null_mut(), dest)))
+    }
+
+    /// Get the largest document size in bytes the printer accepts
+    ///
+    /// Based on the printer's `job-k-octets-supported` attribute; `None` if
+    /// the printer doesn't advertise a limit.
+    pub fn max_document_size(&self) -> Result<Option<usize>> {
+        self.with_info(|info, dest| Ok(info.max_document_size(ptr::null_mut(), dest)))
+    }
+
+    /// Get the supported print resolutions
+    pub fn supported_resolutions(&self) -> Result<Vec<Resolution>> {
+        self.with_info(|info, dest| info.get_supported_resolutions(ptr::null_mut(), dest))
+    }
+
+    /// Get the options that can be used when creating jobs
+    pub fn supported_options(&self) -> Result<Vec<String>> {
+        self.with_info(|info, dest| info.get_supported_options(ptr::null_mut(), dest))
+    }
+
+    /// Get every job creation option with its supported values
+    pub fn all_supported(&self) -> Result<HashMap<String, Vec<String>>> {
+        self.with_info(|info, dest| info.all_supported(ptr::null_mut(), dest))
+    }
+
+    /// Get the localized name of an option
+    pub fn localize_option(&self, option: &str) -> Result<String> {
+        self.with_info(|info, dest| info.localize_option(ptr::null_mut(), dest, option))
+    }
+
+    /// Get the localized name of an option value
+    pub fn localize_value(&self, option: &str, value: &str) -> Result<String> {
+        self.with_info(|info, dest| info.localize_value(ptr::null_mut(), dest, option, value))
+    }
+
+    /// Get the localized name of every job creation option
+    ///
+    /// Maps each option keyword from [`Destination::supported_options`] to its
+    /// display name. Options without a translation map to the keyword itself.
+    pub fn localized_options(&self) -> Result<HashMap<String, String>> {
+        self.with_info(|info, dest| {
+            let options = info.get_supported_options(ptr::null_mut(), dest)?;
+            Ok(options
+                .into_iter()
+                .map(|option| {
+                    let label = info
+                        .localize_option(ptr::null_mut(), dest, &option)
+                        .unwrap_or_else(|_| option.clone());
+                    (option, label)
+                })
+                .collect())
+        })
+    }
+
+    /// Get the localized name of every supported value of an option
+    ///
+    /// Maps each value from [`Destination::supported_values`] to its display
+    /// name. Values without a translation map to the value itself.
+    pub fn localized_values(&self, option: &str) -> Result<HashMap<String, String>> {
+        self.with_info(|info, dest| {
+            let values = info.get_supported_values(ptr::null_mut(), dest, option)?;
+            Ok(values
+                .into_iter()
+                .map(|value| {
+                    let label = info
+                        .localize_value(ptr::null_mut(), dest, option, &value)
+                        .unwrap_or_else(|_| value.clone());
+                    (value, label)
+                })
+                .collect())
+        })
+    }
+
+    /// Get the localized name of a media size
+    pub fn localize_media(&self, size: &MediaSize) -> Result<String> {
+        self.with_info(|info, dest| {
+            info.localize_media(ptr::null_mut(), dest, MEDIA_FLAGS_DEFAULT, size)
+        })
+    }
+
+    fn with_info<T>(
+        &self,
+        query: impl FnOnce(&DestinationInfo, *mut bindings::cups_dest_s) -> Result<T>,
+    ) -> Result<T> {
+        let raw_dest = self.to_raw()?;
+        let info = self.get_detailed_info_for(ptr::null_mut(), &raw_dest)?;
+        query(&info, raw_dest.as_ptr())
+    }
+}
@@ -1,6 +1,9 @@
 use crate::bindings;
 use crate::destination::media_size::MediaSize;
 use crate::error::{Error, Result};
+use crate::ipp::IppAttribute;
+use crate::job::{Finishing, Resolution, ResolutionUnit};
+use std::collections::HashMap;
 use std::ffi::{CStr, CString};
 use std::marker::PhantomData;
 use std::ptr;
@@ -341,57 +344,61 @@ impl DestinationInfo {
         }
     }
 
-    /// Get ready (loaded) media
+    /// Get the ready (loaded) values of any option
     /// 
-    /// Returns the media sizes that are currently loaded/ready in the printer.
-    /// This is different from supported media - ready media are the ones actually
-    /// available for immediate use.
-    pub fn get_ready_media(
+    /// Returns the values that are available right now rather than merely
+    /// supported, e.g. the trays with paper for `media-source` or the loaded
+    /// `media-type`. Values are formatted according to their IPP type; see
+    /// [`IppAttribute::value_as_string`].
+    pub fn get_ready_values(
         &self,
         http: *mut bindings::_http_s,
         dest: *mut bindings::cups_dest_s,
-    ) -> Result<Vec<MediaSize>> {
-        let option_c = match CString::new("media") {
-            Ok(s) => s,
-            Err(_) => return Ok(Vec::new()),
-        };
+        option: &str,
+    ) -> Result<Vec<String>> {
+        let option_c = CString::new(option)?;
 
         let ready_attr = unsafe {
             bindings::cupsFindDestReady(http, dest, self.dinfo, option_c.as_ptr())
         };
 
-        if ready_attr.is_null() {
+        let Some(attr) = IppAttribute::from_raw(ready_attr) else {
             return Ok(Vec::new());
-        }
+        };
 
-        let mut ready_media = Vec::new();
-        let count = unsafe { bindings::ippGetCount(ready_attr) };
-        
-        for i in 0..count {
-            unsafe {
-                let media_name_ptr = bindings::ippGetString(ready_attr, i, ptr::null_mut());
-                if !media_name_ptr.is_null() {
-                    let media_name = CStr::from_ptr(media_name_ptr).to_string_lossy();
-                    
-                    // Try to get the full media size info for this ready media
-                    match self.get_media_by_name(http, dest, &media_name, 0) {
-                        Ok(size) => ready_media.push(size),
-                        Err(_) => {
-                            // If we can't get full info, create a basic MediaSize
-                            ready_media.push(MediaSize {
-                                name: media_name.into_owned(),
-                                width: 0,
-                                length: 0,
-                                left: 0,
-                                bottom: 0,
-                                right: 0,
-                                top: 0,
-                            });
-                        }
-                    }
-                }
-            }
-        }
+        Ok((0..attr.count())
+            .filter_map(|i| attr.value_as_string(i))
+            .collect())
+    }
+
+    /// Get ready (loaded) media
+    /// 
+    /// Returns the media sizes that are currently loaded/ready in the printer.
+    /// This is different from supported media - ready media are the ones actually
+    /// available for immediate use.
+    pub fn get_ready_media(
+        &self,
+        http: *mut bindings::_http_s,
+        dest: *mut bindings::cups_dest_s,
+    ) -> Result<Vec<MediaSize>> {
+        let ready_media = self
+            .get_ready_values(http, dest, "media")?
+            .into_iter()
+            .map(|media_name| {
+                // Try to get the full media size info for this ready media
+                self.get_media_by_name(http, dest, &media_name, 0)
+                    .unwrap_or(MediaSize {
+                        // If we can't get full info, create a basic MediaSize
+                        name: media_name,
+                        width: 0,
+                        length: 0,
+                        left: 0,
+                        bottom: 0,
+                        right: 0,
+                        top: 0,
+                    })
+            })
+            .collect();
 
         Ok(ready_media)
     }
@@ -400,32 +407,19 @@ impl DestinationInfo {
     /// 
     /// Returns the finishing processes that are currently ready/available.
     /// For example, if a printer has staple and punch finishers but is out of staples,
-    /// this will only return punch options.
+    /// this will only return punch options. Finishing values not covered by
+    /// [`Finishing`] are skipped.
     pub fn get_ready_finishings(
         &self,
         http: *mut bindings::_http_s,
         dest: *mut bindings::cups_dest_s,
-    ) -> Result<Vec<i32>> {
-        let option_c = match CString::new("finishings") {
-            Ok(s) => s,
-            Err(_) => return Ok(Vec::new()),
-        };
-
-        let ready_attr = unsafe {
-            bindings::cupsFindDestReady(http, dest, self.dinfo, option_c.as_ptr())
-        };
-
-        if ready_attr.is_null() {
-            return Ok(Vec::new());
-        }
-
-        let mut ready_finishings = Vec::new();
-        let count = unsafe { bindings::ippGetCount(ready_attr) };
-        
-        for i in 0..count {
-            let finishing = unsafe { bindings::ippGetInteger(ready_attr, i) };
-            ready_finishings.push(finishing);
-        }
+    ) -> Result<Vec<Finishing>> {
+        let ready_finishings = self
+            .get_ready_values(http, dest, "finishings")?
+            .iter()
+            .filter_map(|value| value.parse().ok())
+            .filter_map(Finishing::from_ipp)
+            .collect();
 
         Ok(ready_finishings)
     }
@@ -519,6 +513,117 @@ impl DestinationInfo {
         Ok(supported_values)
     }
 
+    /// Get supported print resolutions
+    ///
+    /// Parses the `printer-resolution-supported` attribute. Returns an empty
+    /// list if the destination does not report resolutions.
+    pub fn get_supported_resolutions(
+        &self,
+        http: *mut bindings::_http_s,
+        dest: *mut bindings::cups_dest_s,
+    ) -> Result<Vec<Resolution>> {
+        let option_c = CString::new("printer-resolution")?;
+
+        let supported_attr = unsafe {
+            bindings::cupsFindDestSupported(http, dest, self.dinfo, option_c.as_ptr())
+        };
+
+        if supported_attr.is_null() {
+            return Ok(Vec::new());
+        }
+
+        let mut resolutions = Vec::new();
+        let count = unsafe { bindings::ippGetCount(supported_attr) };
+
+        for i in 0..count {
+            let mut yres = 0;
+            let mut units: bindings::ipp_res_t = 0;
+            let xres = unsafe { bindings::ippGetResolution(supported_attr, i, &mut yres, &mut units) };
+
+            if xres > 0 && yres > 0 {
+                resolutions.push(Resolution {
+                    x: xres as u32,
+                    y: yres as u32,
+                    unit: ResolutionUnit::from_ipp(units),
+                });
+            }
+        }
+
+        Ok(resolutions)
+    }
+
+    /// Get the default print resolution
+    ///
+    /// Parses the `printer-resolution-default` attribute, which
+    /// `get_default_value` can't represent as a string. Returns `None` if the
+    /// destination does not report one.
+    pub fn get_default_resolution(
+        &self,
+        http: *mut bindings::_http_s,
+        dest: *mut bindings::cups_dest_s,
+    ) -> Result<Option<Resolution>> {
+        let option_c = CString::new("printer-resolution")?;
+
+        let default_attr = unsafe {
+            bindings::cupsFindDestDefault(http, dest, self.dinfo, option_c.as_ptr())
+        };
+
+        if default_attr.is_null() {
+            return Ok(None);
+        }
+
+        let mut yres = 0;
+        let mut units: bindings::ipp_res_t = 0;
+        let xres = unsafe { bindings::ippGetResolution(default_attr, 0, &mut yres, &mut units) };
+
+        if xres > 0 && yres > 0 {
+            Ok(Some(Resolution {
+                x: xres as u32,
+                y: yres as u32,
+                unit: ResolutionUnit::from_ipp(units),
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Get the range of copy counts the destination accepts
+    ///
+    /// Parses the `copies-supported` range, e.g. `(1, 999)`. Returns `None` if
+    /// the destination does not report one.
+    pub fn copies_range(
+        &self,
+        http: *mut bindings::_http_s,
+        dest: *mut bindings::cups_dest_s,
+    ) -> Option<(i32, i32)> {
+        let option_c = CString::new("copies").ok()?;
+
+        let supported_attr = unsafe {
+            bindings::cupsFindDestSupported(http, dest, self.dinfo, option_c.as_ptr())
+        };
+
+        IppAttribute::from_raw(supported_attr)?.get_range(0)
+    }
+
+    /// Get the largest document size in bytes the destination accepts
+    ///
+    /// Parses the upper bound of the `job-k-octets-supported` range, which is
+    /// given in kilobytes. Returns `None` if the destination does not report one.
+    pub fn max_document_size(
+        &self,
+        http: *mut bindings::_http_s,
+        dest: *mut bindings::cups_dest_s,
+    ) -> Option<usize> {
+        let option_c = CString::new("job-k-octets").ok()?;
+
+        let supported_attr = unsafe {
+            bindings::cupsFindDestSupported(http, dest, self.dinfo, option_c.as_ptr())
+        };
+
+        let (_, max_k_octets) = IppAttribute::from_raw(supported_attr)?.get_range(0)?;
+        Some((max_k_octets.max(0) as usize).saturating_mul(1024))
+    }
+
     /// Get supported options for job creation
     /// 
     /// Returns a list of all options that can be used when creating jobs
@@ -530,6 +635,26 @@ impl DestinationInfo {
     ) -> Result<Vec<String>> {
         self.get_supported_values(http, dest, "job-creation-attributes")
     }
+
+    /// Get every job creation option with its supported values
+    ///
+    /// Enumerates `job-creation-attributes` and fetches the supported values of
+    /// each option. Options such as `job-name` that accept free-form values map
+    /// to an empty list.
+    pub fn all_supported(
+        &self,
+        http: *mut bindings::_http_s,
+        dest: *mut bindings::cups_dest_s,
+    ) -> Result<HashMap<String, Vec<String>>> {
+        let mut supported = HashMap::new();
+
+        for option in self.get_supported_options(http, dest)? {
+            let values = self.get_supported_values(http, dest, &option)?;
+            supported.insert(option, values);
+        }
+
+        Ok(supported)
+    }
 }
 
 impl Drop for DestinationInfo {
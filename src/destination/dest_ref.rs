@@ -0,0 +1,172 @@
+use super::{Destination, PrinterState};
+use crate::bindings;
+use std::borrow::Cow;
+use std::ffi::CStr;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// A borrowed, zero-copy view of one entry in a [`Destinations`](super::Destinations) list
+///
+/// Unlike [`Destination`], which copies the name, instance and every option
+/// into owned strings, a `DestinationRef` reads straight from the CUPS
+/// `cups_dest_t` array and only converts the fields that are asked for.
+/// Strings are returned as `Cow<str>`, borrowed unless they contain invalid
+/// UTF-8.
+///
+/// A `DestinationRef` borrows the `Destinations` it came from and cannot
+/// outlive it; call [`DestinationRef::to_destination`] to keep an owned copy.
+#[derive(Clone, Copy)]
+pub struct DestinationRef<'a> {
+    dest: *const bindings::cups_dest_s,
+    _marker: PhantomData<&'a bindings::cups_dest_s>,
+}
+
+impl<'a> DestinationRef<'a> {
+    /// Borrow a raw cups_dest_t, or `None` if it has no name
+    ///
+    /// The caller must ensure the structure stays valid and unmodified for `'a`.
+    pub(crate) unsafe fn from_raw(dest_ptr: *const bindings::cups_dest_s) -> Option<Self> {
+        if dest_ptr.is_null() || unsafe { (*dest_ptr).name.is_null() } {
+            return None;
+        }
+
+        Some(DestinationRef {
+            dest: dest_ptr,
+            _marker: PhantomData,
+        })
+    }
+
+    fn raw(&self) -> &'a bindings::cups_dest_s {
+        unsafe { &*self.dest }
+    }
+
+    /// Get the name of the destination
+    pub fn name(&self) -> Cow<'a, str> {
+        unsafe { CStr::from_ptr(self.raw().name) }.to_string_lossy()
+    }
+
+    /// Get the instance name, or `None` for the default instance
+    pub fn instance(&self) -> Option<Cow<'a, str>> {
+        let instance = self.raw().instance;
+        if instance.is_null() {
+            None
+        } else {
+            Some(unsafe { CStr::from_ptr(instance) }.to_string_lossy())
+        }
+    }
+
+    /// Get the full name of this destination (including instance if any)
+    pub fn full_name(&self) -> String {
+        match self.instance() {
+            Some(inst) => format!("{}/{}", self.name(), inst),
+            None => self.name().into_owned(),
+        }
+    }
+
+    /// Check if this is the default destination
+    pub fn is_default(&self) -> bool {
+        self.raw().is_default != 0
+    }
+
+    /// Get an option value by name
+    pub fn get_option(&self, name: &str) -> Option<Cow<'a, str>> {
+        self.options()
+            .find(|(option, _)| option == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Iterate over the destination's options as name/value pairs
+    pub fn options(&self) -> impl Iterator<Item = (Cow<'a, str>, Cow<'a, str>)> + 'a {
+        let dest = self.raw();
+        let count = if dest.options.is_null() {
+            0
+        } else {
+            dest.num_options.max(0) as usize
+        };
+
+        (0..count).filter_map(move |i| unsafe {
+            let option = &*dest.options.add(i);
+            if option.name.is_null() || option.value.is_null() {
+                None
+            } else {
+                Some((
+                    CStr::from_ptr(option.name).to_string_lossy(),
+                    CStr::from_ptr(option.value).to_string_lossy(),
+                ))
+            }
+        })
+    }
+
+    /// Get the state of this destination
+    pub fn state(&self) -> PrinterState {
+        match self.get_option("printer-state") {
+            Some(state) => PrinterState::from_cups_state(&state),
+            None => PrinterState::Unknown,
+        }
+    }
+
+    /// Check if the destination is accepting jobs
+    pub fn is_accepting_jobs(&self) -> bool {
+        self.get_option("printer-is-accepting-jobs").as_deref() == Some("true")
+    }
+
+    /// Get a human-readable description of this destination
+    pub fn info(&self) -> Option<Cow<'a, str>> {
+        self.get_option("printer-info")
+    }
+
+    /// Get the location of this destination
+    pub fn location(&self) -> Option<Cow<'a, str>> {
+        self.get_option("printer-location")
+    }
+
+    /// Copy this entry into an owned [`Destination`]
+    pub fn to_destination(&self) -> Destination {
+        unsafe { Destination::from_raw(self.dest) }
+            .expect("DestinationRef always points to a named destination")
+    }
+}
+
+impl fmt::Debug for DestinationRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DestinationRef")
+            .field("name", &self.name())
+            .field("instance", &self.instance())
+            .field("is_default", &self.is_default())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_destination_ref_fields() {
+        let dest = Destination::new("Office")
+            .with_option("printer-state", "3")
+            .with_option("printer-info", "Office Laser")
+            .with_option("printer-is-accepting-jobs", "true");
+        let raw = dest.as_ptr();
+        let dest_ref = unsafe { DestinationRef::from_raw(raw.as_ptr()) }.unwrap();
+
+        assert_eq!(dest_ref.name(), "Office");
+        assert_eq!(dest_ref.instance(), None);
+        assert_eq!(dest_ref.full_name(), "Office");
+        assert!(!dest_ref.is_default());
+        assert_eq!(dest_ref.state(), PrinterState::Idle);
+        assert!(dest_ref.is_accepting_jobs());
+        assert_eq!(dest_ref.info().as_deref(), Some("Office Laser"));
+        assert_eq!(dest_ref.location(), None);
+        assert_eq!(dest_ref.options().count(), 3);
+
+        let owned = dest_ref.to_destination();
+        assert_eq!(owned, dest);
+        assert_eq!(owned.options, dest.options);
+    }
+
+    #[test]
+    fn test_destination_ref_null() {
+        assert!(unsafe { DestinationRef::from_raw(std::ptr::null()) }.is_none());
+    }
+}
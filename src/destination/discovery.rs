@@ -0,0 +1,122 @@
+use super::{Destination, enum_destinations_raw};
+use crate::constants;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, TryRecvError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Destinations found by [`discover_destinations`] on a background thread
+///
+/// Each destination is delivered as CUPS reports it. Discovery runs until its
+/// timeout expires, [`DestinationStream::cancel`] is called or the stream is
+/// dropped; iterating the stream ends once discovery has stopped, so a `for`
+/// loop over the stream finishes within the timeout.
+pub struct DestinationStream {
+    receiver: Receiver<Destination>,
+    cancel: Arc<AtomicI32>,
+    handle: Option<JoinHandle<()>>,
+}
+
+/// Discover destinations without blocking the calling thread
+///
+/// Runs `cupsEnumDests` on a background thread for up to `timeout`, with the
+/// same `type_filter` and `mask` semantics as
+/// [`enum_destinations`](super::enum_destinations). Only additions are
+/// delivered; use [`enumerate_destinations`](super::enumerate_destinations)
+/// when you need a list that reflects removals.
+pub fn discover_destinations(timeout: Duration, type_filter: u32, mask: u32) -> DestinationStream {
+    // cupsEnumDests treats -1 as "forever", so keep the timeout finite
+    let msec = timeout.as_millis().min(i32::MAX as u128) as i32;
+    let (mut sender, receiver) = mpsc::channel();
+    let cancel = Arc::new(AtomicI32::new(0));
+    let thread_cancel = Arc::clone(&cancel);
+
+    let handle = thread::spawn(move || {
+        // CUPS polls this flag while waiting for network printers to respond
+        let cancel_ptr = thread_cancel.as_ptr();
+
+        let result = enum_destinations_raw(
+            constants::DEST_FLAGS_NONE,
+            msec,
+            cancel_ptr,
+            type_filter,
+            mask,
+            &mut |flags, dest, sender: &mut mpsc::Sender<Destination>| {
+                if (flags & constants::DEST_FLAGS_REMOVED) != 0 {
+                    return true;
+                }
+                // Stop once nobody is listening any more
+                sender.send(dest.clone()).is_ok()
+            },
+            &mut sender,
+        );
+
+        if let Err(e) = result
+            && thread_cancel.load(Ordering::SeqCst) == 0
+        {
+            eprintln!("Warning: Destination discovery stopped: {}", e);
+        }
+    });
+
+    DestinationStream {
+        receiver,
+        cancel,
+        handle: Some(handle),
+    }
+}
+
+impl DestinationStream {
+    /// Stop discovery
+    ///
+    /// Destinations already found can still be received.
+    pub fn cancel(&self) {
+        self.cancel.store(1, Ordering::SeqCst);
+    }
+
+    /// Check if discovery has stopped
+    pub fn is_finished(&self) -> bool {
+        self.handle.as_ref().is_none_or(|handle| handle.is_finished())
+    }
+
+    /// Get the next destination without waiting
+    ///
+    /// Returns `None` if nothing new has been found yet or discovery has stopped.
+    pub fn try_next(&self) -> Option<Destination> {
+        match self.receiver.try_recv() {
+            Ok(dest) => Some(dest),
+            Err(TryRecvError::Empty) | Err(TryRecvError::Disconnected) => None,
+        }
+    }
+
+    /// Wait up to `timeout` for the next destination
+    pub fn next_timeout(&self, timeout: Duration) -> Option<Destination> {
+        match self.receiver.recv_timeout(timeout) {
+            Ok(dest) => Some(dest),
+            Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => None,
+        }
+    }
+
+    /// Get the underlying channel receiver
+    pub fn receiver(&self) -> &Receiver<Destination> {
+        &self.receiver
+    }
+}
+
+impl Iterator for DestinationStream {
+    type Item = Destination;
+
+    /// Wait for the next destination, returning `None` once discovery has stopped
+    fn next(&mut self) -> Option<Destination> {
+        self.receiver.recv().ok()
+    }
+}
+
+impl Drop for DestinationStream {
+    fn drop(&mut self) {
+        self.cancel();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
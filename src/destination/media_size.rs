@@ -1,9 +1,34 @@
 use crate::bindings;
+use crate::constants::*;
 use crate::error::Result;
+use crate::units::{hundredths_mm_to_inches, hundredths_mm_to_mm, mm_to_hundredths_mm};
 use std::ffi::CStr;
 
+/// Allowed difference, in hundredths of millimeters, when matching standard sizes
+const STANDARD_SIZE_TOLERANCE: i32 = 100;
+
+/// Standard media names with their width and length in hundredths of millimeters
+const STANDARD_SIZES: &[(&str, i32, i32)] = &[
+    (MEDIA_3X5, 7620, 12700),
+    (MEDIA_4X6, 10160, 15240),
+    (MEDIA_5X7, 12700, 17780),
+    (MEDIA_8X10, 20320, 25400),
+    (MEDIA_A3, 29700, 42000),
+    (MEDIA_A4, 21000, 29700),
+    (MEDIA_A5, 14800, 21000),
+    (MEDIA_A6, 10500, 14800),
+    (MEDIA_ENV10, 10478, 24130),
+    (MEDIA_ENVDL, 11000, 22000),
+    (MEDIA_LEGAL, 21590, 35560),
+    (MEDIA_LETTER, 21590, 27940),
+    (MEDIA_PHOTO_L, 8890, 12700),
+    (MEDIA_SUPERBA3, 33020, 48260),
+    (MEDIA_TABLOID, 27940, 43180),
+];
+
 /// Media size information from CUPS
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MediaSize {
     /// Media name (e.g., "na_letter_8.5x11in")
     pub name: String,
@@ -45,64 +70,143 @@ impl MediaSize {
         })
     }
 
+    /// Create a custom media size from dimensions in millimeters
+    ///
+    /// The size gets a PWG self-describing name of the form
+    /// `custom_WxHmm_WxHmm` (`class_name_WxHmm`, as `pwgFormatSizeName`
+    /// produces), which CUPS parses back into dimensions, and no margins.
+    pub fn custom(width_mm: f64, length_mm: f64) -> Self {
+        let width = mm_to_hundredths_mm(width_mm);
+        let length = mm_to_hundredths_mm(length_mm);
+        let size = format!(
+            "{}x{}mm",
+            hundredths_mm_to_mm(width),
+            hundredths_mm_to_mm(length)
+        );
+
+        MediaSize {
+            name: format!("custom_{}_{}", size, size),
+            width,
+            length,
+            bottom: 0,
+            left: 0,
+            right: 0,
+            top: 0,
+        }
+    }
+
+    /// Find the standard media name matching these dimensions
+    ///
+    /// Compares against the `MEDIA_*` sizes within 1mm, in either orientation.
+    pub fn standard_name(&self) -> Option<&'static str> {
+        let matches = |a: i32, b: i32| (a - b).abs() <= STANDARD_SIZE_TOLERANCE;
+
+        STANDARD_SIZES
+            .iter()
+            .find(|&&(_, width, length)| {
+                (matches(self.width, width) && matches(self.length, length))
+                    || (matches(self.width, length) && matches(self.length, width))
+            })
+            .map(|&(name, _, _)| name)
+    }
+
+    /// Get the keyword to pass as the `media` option when submitting a job
+    ///
+    /// This is the size's `name`, e.g. `"iso_a4_210x297mm"`; see
+    /// [`PrintOptions::media_size`](crate::PrintOptions::media_size).
+    pub fn as_media_option(&self) -> &str {
+        &self.name
+    }
+
+    /// Check if all margins are zero, allowing edge-to-edge printing
+    pub fn is_borderless(&self) -> bool {
+        self.left == 0 && self.right == 0 && self.top == 0 && self.bottom == 0
+    }
+
+    /// Check if the media is wider than it is long
+    pub fn is_landscape(&self) -> bool {
+        self.length < self.width
+    }
+
+    /// Get the same media turned 90 degrees clockwise
+    ///
+    /// Width and length are swapped and each margin moves to the next edge,
+    /// so the old left margin becomes the top margin.
+    pub fn rotated(&self) -> MediaSize {
+        MediaSize {
+            name: self.name.clone(),
+            width: self.length,
+            length: self.width,
+            bottom: self.right,
+            left: self.bottom,
+            right: self.top,
+            top: self.left,
+        }
+    }
+
+    /// Area in square millimeters
+    pub fn area_mm2(&self) -> f64 {
+        self.width_mm() * self.length_mm()
+    }
+
     /// Width in inches
     pub fn width_inches(&self) -> f64 {
-        self.width as f64 / 2540.0
+        hundredths_mm_to_inches(self.width)
     }
 
     /// Length (height) in inches
     pub fn length_inches(&self) -> f64 {
-        self.length as f64 / 2540.0
+        hundredths_mm_to_inches(self.length)
     }
 
     /// Bottom margin in inches
     pub fn bottom_margin_inches(&self) -> f64 {
-        self.bottom as f64 / 2540.0
+        hundredths_mm_to_inches(self.bottom)
     }
 
     /// Left margin in inches
     pub fn left_margin_inches(&self) -> f64 {
-        self.left as f64 / 2540.0
+        hundredths_mm_to_inches(self.left)
     }
 
     /// Right margin in inches
     pub fn right_margin_inches(&self) -> f64 {
-        self.right as f64 / 2540.0
+        hundredths_mm_to_inches(self.right)
     }
 
     /// Top margin in inches
     pub fn top_margin_inches(&self) -> f64 {
-        self.top as f64 / 2540.0
+        hundredths_mm_to_inches(self.top)
     }
 
     /// Width in millimeters
     pub fn width_mm(&self) -> f64 {
-        self.width as f64 / 100.0
+        hundredths_mm_to_mm(self.width)
     }
 
     /// Length (height) in millimeters
     pub fn length_mm(&self) -> f64 {
-        self.length as f64 / 100.0
+        hundredths_mm_to_mm(self.length)
     }
 
     /// Bottom margin in millimeters
     pub fn bottom_margin_mm(&self) -> f64 {
-        self.bottom as f64 / 100.0
+        hundredths_mm_to_mm(self.bottom)
     }
 
     /// Left margin in millimeters
     pub fn left_margin_mm(&self) -> f64 {
-        self.left as f64 / 100.0
+        hundredths_mm_to_mm(self.left)
     }
 
     /// Right margin in millimeters
     pub fn right_margin_mm(&self) -> f64 {
-        self.right as f64 / 100.0
+        hundredths_mm_to_mm(self.right)
     }
 
     /// Top margin in millimeters
     pub fn top_margin_mm(&self) -> f64 {
-        self.top as f64 / 100.0
+        hundredths_mm_to_mm(self.top)
     }
 
     /// Printable width in hundredths of millimeters
@@ -117,12 +221,12 @@ impl MediaSize {
 
     /// Printable width in inches
     pub fn printable_width_inches(&self) -> f64 {
-        self.printable_width() as f64 / 2540.0
+        hundredths_mm_to_inches(self.printable_width())
     }
 
     /// Printable length in inches
     pub fn printable_length_inches(&self) -> f64 {
-        self.printable_length() as f64 / 2540.0
+        hundredths_mm_to_inches(self.printable_length())
     }
 }
 
@@ -155,4 +259,78 @@ mod tests {
         assert_eq!(media.printable_width(), 21590 - 635 - 635);
         assert_eq!(media.printable_length(), 27940 - 635 - 635);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_media_size_custom() {
+        let media = MediaSize::custom(100.0, 150.5);
+        assert_eq!(media.name, "custom_100x150.5mm_100x150.5mm");
+        assert_eq!(media.width, 10000);
+        assert_eq!(media.length, 15050);
+        assert_eq!(media.printable_width(), media.width);
+        assert_eq!(media.standard_name(), None);
+        assert_eq!(media.as_media_option(), "custom_100x150.5mm_100x150.5mm");
+    }
+
+    #[test]
+    fn test_media_size_standard_name() {
+        assert_eq!(MediaSize::custom(210.0, 297.0).standard_name(), Some(MEDIA_A4));
+        assert_eq!(MediaSize::custom(297.0, 210.0).standard_name(), Some(MEDIA_A4));
+        assert_eq!(MediaSize::custom(215.9, 279.4).standard_name(), Some(MEDIA_LETTER));
+        assert_eq!(MediaSize::custom(216.0, 356.0).standard_name(), Some(MEDIA_LEGAL));
+        assert_eq!(MediaSize::custom(110.0, 220.0).standard_name(), Some(MEDIA_ENVDL));
+        assert_eq!(MediaSize::custom(200.0, 200.0).standard_name(), None);
+    }
+
+    #[test]
+    fn test_media_size_rotated() {
+        let media = MediaSize {
+            name: "iso_a4_210x297mm".to_string(),
+            width: 21000,
+            length: 29700,
+            bottom: 100,
+            left: 200,
+            right: 300,
+            top: 400,
+        };
+        assert!(!media.is_landscape());
+
+        let rotated = media.rotated();
+        assert!(rotated.is_landscape());
+        assert_eq!(rotated.width, 29700);
+        assert_eq!(rotated.length, 21000);
+        assert_eq!(rotated.top, 200);
+        assert_eq!(rotated.right, 400);
+        assert_eq!(rotated.bottom, 300);
+        assert_eq!(rotated.left, 100);
+        assert_eq!(rotated.printable_width(), media.printable_length());
+        assert_eq!(rotated.rotated().rotated().rotated().rotated(), rotated);
+
+        assert!((media.area_mm2() - 62370.0).abs() < 0.01);
+        assert!((rotated.area_mm2() - media.area_mm2()).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_media_size_equality() {
+        let letter = MediaSize {
+            name: "na_letter_8.5x11in".to_string(),
+            width: 21590,
+            length: 27940,
+            bottom: 635,
+            left: 635,
+            right: 635,
+            top: 635,
+        };
+        let borderless = MediaSize {
+            bottom: 0,
+            left: 0,
+            right: 0,
+            top: 0,
+            ..letter.clone()
+        };
+
+        assert_eq!(letter, letter.clone());
+        assert_ne!(letter, borderless);
+        assert!(!letter.is_borderless());
+        assert!(borderless.is_borderless());
+    }
+}
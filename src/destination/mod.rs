@@ -1,15 +1,30 @@
+mod admin;
+mod capabilities;
 mod dest_info;
+mod dest_ref;
+mod discovery;
 mod media_size;
+mod ppd;
+mod printer_capabilities;
+mod printer_defaults;
 mod printer_state;
+mod raw_dest;
 
 pub use dest_info::DestinationInfo;
+pub use dest_ref::DestinationRef;
+pub use discovery::{DestinationStream, discover_destinations};
 pub use media_size::MediaSize;
-pub use printer_state::PrinterState;
+pub use ppd::remove_ppd;
+pub use printer_capabilities::PrinterCapabilities;
+pub use printer_defaults::PrinterDefaults;
+pub use printer_state::{PrinterState, Severity, StateReason};
+pub use raw_dest::RawDest;
 
 use crate::bindings;
+use crate::connection::HttpConnection;
 use crate::constants;
 use crate::error::{Error, Result};
-use crate::error_helpers::cups_error_to_our_error;
+use crate::error_helpers::{cups_error_to_our_error, get_cups_error_details};
 use std::collections::HashMap;
 use std::ffi::{CStr, CString};
 use std::marker::PhantomData;
@@ -19,7 +34,12 @@ use std::ptr;
 pub type DestCallback<T> = dyn FnMut(u32, &Destination, &mut T) -> bool;
 
 /// Represents a printer or class of printers available for printing
-#[derive(Debug, Clone)]
+///
+/// Two destinations are equal when their name and instance match; options
+/// such as the current printer state are not compared, so the same printer
+/// seen in two enumerations deduplicates in a `HashSet`.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Destination {
     /// Name of the destination
     pub name: String,
@@ -32,6 +52,41 @@ pub struct Destination {
 }
 
 impl Destination {
+    /// Create a destination with the given name, no instance and no options
+    ///
+    /// Useful for tests and for naming a queue to look up on a specific server.
+    pub fn new(name: impl Into<String>) -> Self {
+        Destination {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set an option, returning the updated destination
+    pub fn with_option(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.options.insert(name.into(), value.into());
+        self
+    }
+
+    /// Create an idle, job-accepting destination for tests
+    ///
+    /// `printer-state` and `printer-is-accepting-jobs` are preset and can be
+    /// overridden through `options`.
+    #[cfg(feature = "testing")]
+    pub fn mock<K, V>(name: impl Into<String>, options: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let mut dest = Destination::new(name)
+            .with_option("printer-state", PrinterState::Idle.to_ipp_code().to_string())
+            .with_option("printer-is-accepting-jobs", "true");
+        for (name, value) in options {
+            dest = dest.with_option(name, value);
+        }
+        dest
+    }
+
     /// Create a new Destination instance from raw cups_dest_t pointer
     pub(crate) unsafe fn from_raw(dest_ptr: *const bindings::cups_dest_s) -> Result<Self> {
         if dest_ptr.is_null() {
@@ -98,6 +153,34 @@ impl Destination {
         }
     }
 
+    /// Get the reasons for the current state split into keyword and severity
+    ///
+    /// The `none` reason is left out, so an empty list means no problems.
+    pub fn parsed_state_reasons(&self) -> Vec<StateReason> {
+        self.state_reasons()
+            .iter()
+            .filter_map(|reason| StateReason::parse(reason))
+            .collect()
+    }
+
+    /// Check if the printer has been paused, e.g. with [`Destination::pause`]
+    pub fn is_paused(&self) -> bool {
+        self.state_reasons().iter().any(|reason| reason == "paused")
+    }
+
+    /// Get the features advertised in the `printer-type` attribute
+    pub fn capabilities(&self) -> PrinterCapabilities {
+        match self.options.get("printer-type") {
+            Some(value) => PrinterCapabilities::from_printer_type(value),
+            None => PrinterCapabilities::default(),
+        }
+    }
+
+    /// Get the human-readable explanation of the current state, e.g. "Paper jam"
+    pub fn state_message(&self) -> Option<&String> {
+        self.options.get("printer-state-message")
+    }
+
     /// Get a human-readable description of this destination
     pub fn info(&self) -> Option<&String> {
         self.options.get("printer-info")
@@ -121,11 +204,59 @@ impl Destination {
         }
     }
 
+    /// Check if the printer is shared with other computers on the network
+    pub fn is_shared(&self) -> bool {
+        self.get_bool_option("printer-is-shared").unwrap_or(false)
+    }
+
+    /// Check if the printer is a temporary queue created on demand by CUPS
+    pub fn is_temporary(&self) -> bool {
+        self.get_bool_option("printer-is-temporary").unwrap_or(false)
+    }
+
+    /// Check if the printer can print in color
+    pub fn supports_color(&self) -> bool {
+        self.get_bool_option("color-supported").unwrap_or(false)
+    }
+
+    /// Get a boolean option, or `None` if the destination doesn't report it
+    ///
+    /// Only the value `"true"` counts as true, matching how CUPS formats
+    /// boolean attributes in destination options.
+    pub fn get_bool_option(&self, name: &str) -> Option<bool> {
+        self.options.get(name).map(|value| value == "true")
+    }
+
     /// Get the URI associated with this destination
     pub fn uri(&self) -> Option<&String> {
         self.options.get("printer-uri-supported")
     }
 
+    /// Get every URI the printer advertises in `printer-uri-supported`
+    ///
+    /// Printers often list several, e.g. `ipp://`, `ipps://` and `http://`
+    /// variants of the same endpoint.
+    pub fn uris(&self) -> Vec<String> {
+        match self.uri() {
+            Some(uris) => uris
+                .split(',')
+                .map(str::trim)
+                .filter(|uri| !uri.is_empty())
+                .map(str::to_string)
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Get the printer's encrypted URI, preferring `ipps://` over `https://`
+    pub fn secure_uri(&self) -> Option<String> {
+        let uris = self.uris();
+        ["ipps://", "https://"]
+            .iter()
+            .find_map(|scheme| uris.iter().find(|uri| uri.starts_with(scheme)))
+            .cloned()
+    }
+
     /// Get the device URI for this destination
     pub fn device_uri(&self) -> Option<&String> {
         self.options.get("device-uri")
@@ -154,68 +285,87 @@ impl Destination {
         &self.options
     }
 
-    /// Get detailed information about this destination
-    pub fn get_detailed_info(&self, http: *mut bindings::_http_s) -> Result<DestinationInfo> {
-        let name_c = CString::new(self.name.as_str())?;
-        let instance_c = match &self.instance {
-            Some(instance) => Some(CString::new(instance.as_str())?),
-            None => None,
-        };
-
-        let _instance_ptr = match &instance_c {
-            Some(s) => s.as_ptr(),
-            None => ptr::null(),
-        };
-
-        let mut num_options = 0;
-        let mut options_ptr: *mut bindings::cups_option_s = ptr::null_mut();
-
-        for (name, value) in &self.options {
-            let name_c = CString::new(name.as_str())?;
-            let value_c = CString::new(value.as_str())?;
-
-            unsafe {
-                num_options = bindings::cupsAddOption(
-                    name_c.as_ptr(),
-                    value_c.as_ptr(),
-                    num_options,
-                    &mut options_ptr,
-                );
-            }
+    /// Get a multi-line, human-readable description of this destination
+    ///
+    /// Includes the state, description, location, model, whether jobs are
+    /// accepted and any state reasons or message reported by the printer.
+    pub fn summary(&self) -> String {
+        let mut summary = format!("{} ({})", self.full_name(), self.state());
+        if self.is_default {
+            summary.push_str(" [default]");
         }
 
-        let dest = bindings::cups_dest_s {
-            name: name_c.into_raw(),
-            instance: match instance_c {
-                Some(s) => s.into_raw(),
-                None => ptr::null_mut(),
-            },
-            is_default: if self.is_default { 1 } else { 0 },
-            num_options,
-            options: options_ptr,
-        };
+        if let Some(info) = self.info() {
+            summary.push_str(&format!("\n  Description: {}", info));
+        }
+        if let Some(location) = self.location() {
+            summary.push_str(&format!("\n  Location: {}", location));
+        }
+        if let Some(model) = self.make_and_model() {
+            summary.push_str(&format!("\n  Model: {}", model));
+        }
+        summary.push_str(&format!(
+            "\n  Accepting jobs: {}",
+            if self.is_accepting_jobs() { "yes" } else { "no" }
+        ));
+
+        let reasons: Vec<String> = self
+            .state_reasons()
+            .into_iter()
+            .filter(|reason| reason != "none")
+            .collect();
+        if !reasons.is_empty() {
+            summary.push_str(&format!("\n  Issues: {}", reasons.join(", ")));
+        }
+        if let Some(message) = self.state_message() {
+            summary.push_str(&format!("\n  Message: {}", message));
+        }
 
-        let dinfo = unsafe {
-            bindings::cupsCopyDestInfo(
-                http,
-                &dest as *const bindings::cups_dest_s as *mut bindings::cups_dest_s,
-            )
-        };
+        summary
+    }
 
-        unsafe {
-            if !options_ptr.is_null() {
-                bindings::cupsFreeOptions(num_options, options_ptr);
-            }
+    /// Re-read this destination's options from the server
+    ///
+    /// Updates `options` and `is_default` so accessors such as
+    /// [`Destination::state`] and [`Destination::is_accepting_jobs`] reflect the
+    /// printer's current status. Only this destination is fetched, with
+    /// `cupsGetNamedDest`, so polling doesn't re-list every destination. Fails
+    /// with `Error::DestinationNotFound` if the destination no longer exists.
+    pub fn refresh(&mut self) -> Result<()> {
+        let current = Destinations::get_named_on(
+            ptr::null_mut(),
+            &self.name,
+            self.instance.as_deref(),
+        )?;
+
+        self.is_default = current.is_default;
+        self.options = current.options;
+        Ok(())
+    }
 
-            if !dest.name.is_null() {
-                let _ = CString::from_raw(dest.name);
-            }
+    /// Get detailed information about this destination
+    ///
+    /// Pass `ptr::null_mut()` to query the default CUPS server, or
+    /// `connection.as_ptr()` to reuse an open [`HttpConnection`](crate::HttpConnection).
+    pub fn get_detailed_info(&self, http: *mut bindings::_http_s) -> Result<DestinationInfo> {
+        let raw_dest = self.to_raw()?;
+        self.get_detailed_info_for(http, &raw_dest)
+    }
 
-            if !dest.instance.is_null() {
-                let _ = CString::from_raw(dest.instance);
-            }
+    /// Get detailed information using a raw destination the caller already built
+    ///
+    /// Lets operations that also pass the `cups_dest_t` to CUPS build it once.
+    pub(crate) fn get_detailed_info_for(
+        &self,
+        http: *mut bindings::_http_s,
+        raw_dest: &RawDest,
+    ) -> Result<DestinationInfo> {
+        if raw_dest.is_null() {
+            return Err(Error::NullPointer);
         }
 
+        let dinfo = unsafe { bindings::cupsCopyDestInfo(http, raw_dest.as_ptr()) };
+
         if dinfo.is_null() {
             return Err(cups_error_to_our_error(
                 "get destination info",
@@ -228,113 +378,34 @@ impl Destination {
 
     /// Check if a specific option and value is supported by this destination
     pub fn is_option_supported(&self, http: *mut bindings::_http_s, option: &str) -> bool {
-        match self.get_detailed_info(http) {
-            Ok(info) => {
-                // Create a raw cups_dest_t for this destination
-                let name_c = match CString::new(self.name.as_str()) {
-                    Ok(s) => s,
-                    Err(_) => return false,
-                };
-
-                let instance_c = match &self.instance {
-                    Some(instance) => match CString::new(instance.as_str()) {
-                        Ok(s) => Some(s),
-                        Err(_) => return false,
-                    },
-                    None => None,
-                };
-
-                let _instance_ptr = match &instance_c {
-                    Some(s) => s.as_ptr(),
-                    None => ptr::null(),
-                };
-
-                let mut num_options = 0;
-                let mut options_ptr: *mut bindings::cups_option_s = ptr::null_mut();
-
-                // Add all options
-                for (name, value) in &self.options {
-                    let name_c = match CString::new(name.as_str()) {
-                        Ok(s) => s,
-                        Err(_) => continue,
-                    };
-
-                    let value_c = match CString::new(value.as_str()) {
-                        Ok(s) => s,
-                        Err(_) => continue,
-                    };
-
-                    unsafe {
-                        num_options = bindings::cupsAddOption(
-                            name_c.as_ptr(),
-                            value_c.as_ptr(),
-                            num_options,
-                            &mut options_ptr,
-                        );
-                    }
-                }
-
-                let dest = bindings::cups_dest_s {
-                    name: name_c.into_raw(),
-                    instance: match instance_c {
-                        Some(s) => s.into_raw(),
-                        None => ptr::null_mut(),
-                    },
-                    is_default: if self.is_default { 1 } else { 0 },
-                    num_options,
-                    options: options_ptr,
-                };
-
-                // Check if the option is supported
-                let result = info.is_option_supported(
-                    http,
-                    &dest as *const bindings::cups_dest_s as *mut bindings::cups_dest_s,
-                    option,
-                );
-
-                // Free the resources
-                unsafe {
-                    if !options_ptr.is_null() {
-                        bindings::cupsFreeOptions(num_options, options_ptr);
-                    }
-
-                    // Need to free the raw strings we created
-                    if !dest.name.is_null() {
-                        let _ = CString::from_raw(dest.name);
-                    }
-
-                    if !dest.instance.is_null() {
-                        let _ = CString::from_raw(dest.instance);
-                    }
-                }
-
-                result
-            }
+        let raw_dest = self.as_ptr();
+        match self.get_detailed_info_for(http, &raw_dest) {
+            Ok(info) => info.is_option_supported(http, raw_dest.as_ptr(), option),
             Err(_) => false,
         }
     }
 
-    /// Get a pointer to a raw cups_dest_s for this destination
-    pub fn as_ptr(&self) -> *mut bindings::cups_dest_s {
-        // Create a raw cups_dest_t for this destination
-        let name_c = match CString::new(self.name.as_str()) {
-            Ok(s) => s,
-            Err(_) => return ptr::null_mut(),
-        };
+    /// Get a raw cups_dest_s for this destination
+    ///
+    /// The structure is cached per thread and reused while the destination's
+    /// name, instance, default flag and options are unchanged; changing any of
+    /// them builds a new one. The returned guard keeps the structure alive.
+    /// Its pointer is null if the name or instance contains a null byte.
+    pub fn as_ptr(&self) -> RawDest {
+        self.to_raw().unwrap_or_else(|_| RawDest::null())
+    }
 
+    pub(crate) fn to_raw(&self) -> Result<RawDest> {
+        RawDest::cached(self, || self.build_raw())
+    }
+
+    fn build_raw(&self) -> Result<RawDest> {
+        let name_c = CString::new(self.name.as_str())?;
         let instance_c = match &self.instance {
-            Some(instance) => match CString::new(instance.as_str()) {
-                Ok(s) => Some(s),
-                Err(_) => return ptr::null_mut(),
-            },
+            Some(instance) => Some(CString::new(instance.as_str())?),
             None => None,
         };
 
-        let _instance_ptr = match &instance_c {
-            Some(s) => s.as_ptr(),
-            None => ptr::null(),
-        };
-
         let mut num_options = 0;
         let mut options_ptr: *mut bindings::cups_option_s = ptr::null_mut();
 
@@ -360,8 +431,7 @@ impl Destination {
             }
         }
 
-        // Create the raw cups_dest_s struct
-        let dest = Box::new(bindings::cups_dest_s {
+        Ok(RawDest::from_box(Box::new(bindings::cups_dest_s {
             name: name_c.into_raw(),
             instance: match instance_c {
                 Some(s) => s.into_raw(),
@@ -370,10 +440,38 @@ impl Destination {
             is_default: if self.is_default { 1 } else { 0 },
             num_options,
             options: options_ptr,
-        });
+        })))
+    }
+}
+
+impl std::fmt::Display for Destination {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.full_name(), self.state())?;
+
+        let details: Vec<&str> = [self.info(), self.location()]
+            .into_iter()
+            .flatten()
+            .map(|s| s.as_str())
+            .collect();
+        if !details.is_empty() {
+            write!(f, " — {}", details.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq for Destination {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.instance == other.instance
+    }
+}
 
-        // Leak the box to keep the memory alive
-        Box::into_raw(dest)
+impl Eq for Destination {}
+
+impl std::hash::Hash for Destination {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.instance.hash(state);
     }
 }
 
@@ -395,12 +493,18 @@ impl Destinations {
     }
 
     /// Get all available destinations from the default CUPS server
+    ///
+    /// A system with no printers yields an empty list; `Error::DestinationListFailed`
+    /// is only returned when the server could not be reached or failed.
     pub fn get_all() -> Result<Self> {
         let mut dests: *mut bindings::cups_dest_s = ptr::null_mut();
         let num_dests = unsafe { bindings::cupsGetDests(&mut dests) };
 
         if num_dests <= 0 || dests.is_null() {
-            return Err(Error::DestinationListFailed);
+            // cupsGetDests sets the last error on every path that returns no
+            // destinations, so the code belongs to this call
+            let (code, _) = get_cups_error_details();
+            return empty_dest_list(code);
         }
 
         Ok(Destinations {
@@ -411,31 +515,106 @@ impl Destinations {
     }
 
     /// Get a specific destination by name
+    ///
+    /// Accepts `"name/instance"` to get a saved instance of a destination.
     pub fn get_destination<S: AsRef<str>>(name: S) -> Result<Destination> {
+        let (name, instance) = split_full_name(name.as_ref());
+        Self::get_destination_instance(name, instance)
+    }
+
+    /// Get a specific destination, or one of its instances, by name
+    pub fn get_destination_instance(name: &str, instance: Option<&str>) -> Result<Destination> {
         // Get all destinations first
         let all_dests = Self::get_all()?;
 
         // Find the specific destination
-        let name_c = CString::new(name.as_ref())?;
+        let name_c = CString::new(name)?;
+        let instance_c = instance.map(CString::new).transpose()?;
+        let instance_ptr = instance_c.as_ref().map(|c| c.as_ptr()).unwrap_or(ptr::null());
+
         let dest_ptr = unsafe {
             bindings::cupsGetDest(
                 name_c.as_ptr(),
-                ptr::null(),
+                instance_ptr,
                 all_dests.num_dests,
                 all_dests.dests,
             )
         };
 
         if dest_ptr.is_null() {
-            return Err(Error::DestinationNotFound(name.as_ref().to_string()));
+            let full_name = match instance {
+                Some(instance) => format!("{}/{}", name, instance),
+                None => name.to_string(),
+            };
+            return Err(Error::DestinationNotFound(full_name));
         }
 
         // Convert to our Destination type
         unsafe { Destination::from_raw(dest_ptr) }
     }
 
+    /// Get a specific destination by asking the server for it directly
+    ///
+    /// Unlike [`Destinations::get_destination`] this doesn't fetch the whole list
+    /// first, and it also finds network printers CUPS discovers on demand.
+    /// Pass a connection to query that server, or `None` for the default server.
+    pub fn get_named(
+        http: Option<&HttpConnection>,
+        name: &str,
+        instance: Option<&str>,
+    ) -> Result<Destination> {
+        let http_ptr = match http {
+            Some(connection) => connection.checked_ptr()?,
+            None => ptr::null_mut(),
+        };
+        Self::get_named_on(http_ptr, name, instance)
+    }
+
+    /// Ask the server `http` refers to for a destination, or the default server if null
+    pub(crate) fn get_named_on(
+        http_ptr: *mut bindings::_http_s,
+        name: &str,
+        instance: Option<&str>,
+    ) -> Result<Destination> {
+        let name_c = CString::new(name)?;
+        let instance_c = instance.map(CString::new).transpose()?;
+        let instance_ptr = instance_c.as_ref().map(|c| c.as_ptr()).unwrap_or(ptr::null());
+
+        let dest_ptr =
+            unsafe { bindings::cupsGetNamedDest(http_ptr, name_c.as_ptr(), instance_ptr) };
+
+        if dest_ptr.is_null() {
+            let full_name = match instance {
+                Some(instance) => format!("{}/{}", name, instance),
+                None => name.to_string(),
+            };
+            return Err(Error::DestinationNotFound(full_name));
+        }
+
+        let dest = unsafe { Destination::from_raw(dest_ptr) };
+        unsafe { bindings::cupsFreeDests(1, dest_ptr) };
+        dest
+    }
+
     /// Get the default destination
+    ///
+    /// Asks CUPS to resolve the default the same way `lp` does, honouring
+    /// `LPDEST`/`PRINTER`, the user's `lpoptions` default and the server
+    /// default. Falls back to the destination flagged as default in the full
+    /// list if that fails.
     pub fn get_default() -> Result<Destination> {
+        let dest_ptr =
+            unsafe { bindings::cupsGetNamedDest(ptr::null_mut(), ptr::null(), ptr::null()) };
+
+        if !dest_ptr.is_null() {
+            let dest = unsafe { Destination::from_raw(dest_ptr) };
+            unsafe { bindings::cupsFreeDests(1, dest_ptr) };
+            if let Ok(mut dest) = dest {
+                dest.is_default = true;
+                return Ok(dest);
+            }
+        }
+
         // Get all destinations first
         let all_dests = Self::get_all()?;
 
@@ -453,20 +632,34 @@ impl Destinations {
 
     /// Convert to a Vec of Destination objects
     pub fn to_vec(&self) -> Result<Vec<Destination>> {
-        let mut destinations = Vec::with_capacity(self.num_dests as usize);
+        Ok(self.iter().collect())
+    }
 
-        for i in 0..self.num_dests as isize {
-            unsafe {
-                match Destination::from_raw(self.dests.offset(i)) {
-                    Ok(dest) => destinations.push(dest),
-                    Err(e) => {
-                        eprintln!("Warning: Failed to parse destination at index {}: {}", i, e)
-                    }
-                }
-            }
+    /// Iterate over the destinations
+    ///
+    /// Each destination is parsed as it is reached; entries that fail to parse
+    /// are skipped with a warning.
+    pub fn iter(&self) -> DestinationsIter<'_> {
+        DestinationsIter {
+            dests: self,
+            index: 0,
         }
+    }
+
+    /// Iterate over borrowed views of the destinations without copying them
+    ///
+    /// Each [`DestinationRef`] reads directly from this list, so it is cheaper
+    /// than [`Destinations::iter`] when only a few fields are needed. The
+    /// references cannot outlive `self` or be held across changes to it.
+    pub fn refs(&self) -> impl Iterator<Item = DestinationRef<'_>> {
+        let dests = self.dests;
+        let count = if dests.is_null() {
+            0
+        } else {
+            self.num_dests.max(0) as usize
+        };
 
-        Ok(destinations)
+        (0..count).filter_map(move |i| unsafe { DestinationRef::from_raw(dests.add(i)) })
     }
 
     /// Get the number of destinations
@@ -696,19 +889,9 @@ impl DestinationInfo {
         let new_option_c = CString::new(new_option)?;
         let new_value_c = CString::new(new_value)?;
 
-        // Get destination pointer (we need to create one temporarily)
-        let dest_name_c = CString::new(dest.name.as_str())?;
-        let dest_instance_c = dest.instance.as_ref().map(|i| CString::new(i.as_str())).transpose()?;
-        let dest_instance_ptr = dest_instance_c.as_ref().map(|c| c.as_ptr()).unwrap_or(ptr::null());
-
-        let dest_ptr = unsafe {
-            bindings::cupsGetDest(
-                dest_name_c.as_ptr(),
-                dest_instance_ptr,
-                1, // We just need a temporary dest
-                ptr::null_mut(), // Let CUPS find it
-            )
-        };
+        // Keep the raw destination alive until CUPS is done with it
+        let raw_dest = dest.as_ptr();
+        let dest_ptr = raw_dest.as_ptr();
 
         if dest_ptr.is_null() {
             unsafe {
@@ -821,6 +1004,80 @@ impl DestinationInfo {
     }
 }
 
+impl DestinationInfo {
+    /// Get a conflict-free version of a set of options
+    ///
+    /// Options are applied in order; whenever one conflicts with those before
+    /// it, the resolution CUPS suggests is applied. The result can be passed
+    /// straight to job creation. Fails if a conflict cannot be resolved.
+    pub fn resolve_conflicts(
+        &self,
+        dest: &Destination,
+        options: &[(String, String)],
+    ) -> Result<Vec<(String, String)>> {
+        let mut resolved: Vec<(String, String)> = Vec::with_capacity(options.len());
+
+        for (name, value) in options {
+            let conflict = self.check_option_conflicts(dest, &resolved, name, value)?;
+
+            set_option_value(&mut resolved, name, value);
+            if let Some(conflict) = conflict {
+                for (name, value) in &conflict.resolved_options {
+                    set_option_value(&mut resolved, name, value);
+                }
+            }
+        }
+
+        Ok(resolved)
+    }
+}
+
+/// Set an option in a list of pairs, replacing any existing value
+fn set_option_value(options: &mut Vec<(String, String)>, name: &str, value: &str) {
+    match options.iter_mut().find(|(n, _)| n == name) {
+        Some(option) => option.1 = value.to_string(),
+        None => options.push((name.to_string(), value.to_string())),
+    }
+}
+
+impl<'a> IntoIterator for &'a Destinations {
+    type Item = Destination;
+    type IntoIter = DestinationsIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the entries of a [`Destinations`] list
+pub struct DestinationsIter<'a> {
+    dests: &'a Destinations,
+    index: c_int,
+}
+
+impl Iterator for DestinationsIter<'_> {
+    type Item = Destination;
+
+    fn next(&mut self) -> Option<Destination> {
+        while self.index < self.dests.num_dests {
+            let i = self.index;
+            self.index += 1;
+
+            match unsafe { Destination::from_raw(self.dests.dests.offset(i as isize)) } {
+                Ok(dest) => return Some(dest),
+                Err(e) => {
+                    eprintln!("Warning: Failed to parse destination at index {}: {}", i, e)
+                }
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some((self.dests.num_dests - self.index) as usize))
+    }
+}
+
 impl Drop for Destinations {
     fn drop(&mut self) {
         unsafe {
@@ -842,6 +1099,24 @@ pub fn enum_destinations<T>(
     mask: u32,
     callback: &mut DestCallback<T>,
     user_data: &mut T,
+) -> Result<bool> {
+    let cancel_ptr = match cancel {
+        Some(c) => c as *mut c_int,
+        None => ptr::null_mut(),
+    };
+
+    enum_destinations_raw(flags, msec, cancel_ptr, type_filter, mask, callback, user_data)
+}
+
+/// Run `cupsEnumDests` with a raw cancel flag, which may be shared with another thread
+fn enum_destinations_raw<T>(
+    flags: u32,
+    msec: i32,
+    cancel_ptr: *mut c_int,
+    type_filter: u32,
+    mask: u32,
+    callback: &mut DestCallback<T>,
+    user_data: &mut T,
 ) -> Result<bool> {
     // We need to create a context that will be passed to the C callback
     let mut context = EnumContext {
@@ -849,11 +1124,6 @@ pub fn enum_destinations<T>(
         user_data,
     };
 
-    let cancel_ptr = match cancel {
-        Some(c) => c as *mut c_int,
-        None => ptr::null_mut(),
-    };
-
     let result = unsafe {
         bindings::cupsEnumDests(
             flags,
@@ -909,16 +1179,60 @@ unsafe extern "C" fn enum_dest_callback<T>(
     }
 }
 
+/// Interpret the error CUPS reported alongside an empty destination list
+///
+/// CUPS sets `IPP_STATUS_ERROR_NOT_FOUND` ("No destinations added.") when
+/// there are simply no printers, so only server and connection failures
+/// (`IPP_STATUS_ERROR_INTERNAL` and above) are errors.
+fn empty_dest_list(code: i32) -> Result<Destinations> {
+    if code >= bindings::ipp_status_e_IPP_STATUS_ERROR_INTERNAL {
+        return Err(Error::DestinationListFailed);
+    }
+    Ok(Destinations::new())
+}
+
 /// Get all available printer destinations
 pub fn get_all_destinations() -> Result<Vec<Destination>> {
     Destinations::get_all()?.to_vec()
 }
 
-/// Get a specific destination by name
+/// Get all available destinations, giving up on discovery after `timeout_ms`
+///
+/// Unlike [`get_all_destinations`], which can block for a long time while
+/// network printers are discovered, this enumerates destinations with
+/// `cupsEnumDests` and returns whatever was found when the timeout expires.
+pub fn get_all_destinations_timeout(timeout_ms: i32) -> Result<Vec<Destination>> {
+    enumerate_destinations(timeout_ms, 0, 0)
+}
+
+/// Get a specific destination by name, or `"name/instance"` for an instance
 pub fn get_destination<S: AsRef<str>>(name: S) -> Result<Destination> {
     Destinations::get_destination(name)
 }
 
+/// Get a specific destination from a server without listing all destinations
+pub fn get_named_destination(
+    http: Option<&HttpConnection>,
+    name: &str,
+    instance: Option<&str>,
+) -> Result<Destination> {
+    Destinations::get_named(http, name, instance)
+}
+
+/// Get a specific destination, or one of its instances, by name
+pub fn get_destination_instance(name: &str, instance: Option<&str>) -> Result<Destination> {
+    Destinations::get_destination_instance(name, instance)
+}
+
+/// Split a `"name/instance"` destination name into its parts
+pub(crate) fn split_full_name(full_name: &str) -> (&str, Option<&str>) {
+    match full_name.split_once('/') {
+        Some((name, instance)) if !instance.is_empty() => (name, Some(instance)),
+        Some((name, _)) => (name, None),
+        None => (full_name, None),
+    }
+}
+
 /// Get the default destination
 pub fn get_default_destination() -> Result<Destination> {
     Destinations::get_default()
@@ -957,20 +1271,26 @@ pub fn remove_dest(
     Ok(result)
 }
 
-/// Find available destinations with specific filter criteria
-pub fn find_destinations(type_filter: u32, mask: u32) -> Result<Vec<Destination>> {
+/// Enumerate destinations to completion and return the resulting list
+///
+/// Runs [`enum_destinations`] for up to `timeout_ms` milliseconds, applying each
+/// event to the list: additions are appended (or replace an earlier entry for
+/// the same name and instance) and `DEST_FLAGS_REMOVED` events drop the entry.
+pub fn enumerate_destinations(
+    timeout_ms: i32,
+    type_filter: u32,
+    mask: u32,
+) -> Result<Vec<Destination>> {
     let mut destinations = Vec::new();
 
     enum_destinations(
         constants::DEST_FLAGS_NONE,
-        5000, // 5 second timeout
+        timeout_ms,
         None,
         type_filter,
         mask,
         &mut |flags, dest, dests: &mut Vec<Destination>| {
-            if (flags & constants::DEST_FLAGS_REMOVED) == 0 {
-                dests.push(dest.clone());
-            }
+            apply_dest_event(dests, flags, dest);
             true // Continue enumeration
         },
         &mut destinations,
@@ -979,6 +1299,26 @@ pub fn find_destinations(type_filter: u32, mask: u32) -> Result<Vec<Destination>
     Ok(destinations)
 }
 
+/// Apply one enumeration event to a list of destinations
+fn apply_dest_event(dests: &mut Vec<Destination>, flags: u32, dest: &Destination) {
+    let existing = dests.iter().position(|d| d == dest);
+
+    if (flags & constants::DEST_FLAGS_REMOVED) != 0 {
+        if let Some(index) = existing {
+            dests.remove(index);
+        }
+    } else if let Some(index) = existing {
+        dests[index] = dest.clone();
+    } else {
+        dests.push(dest.clone());
+    }
+}
+
+/// Find available destinations with specific filter criteria
+pub fn find_destinations(type_filter: u32, mask: u32) -> Result<Vec<Destination>> {
+    enumerate_destinations(5000, type_filter, mask) // 5 second timeout
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1004,6 +1344,177 @@ mod tests {
         assert_eq!(dest.info(), Some(&"Test Printer".to_string()));
     }
 
+    #[test]
+    fn test_destination_is_paused() {
+        let mut options = std::collections::HashMap::new();
+        options.insert("printer-state".to_string(), "5".to_string());
+        options.insert(
+            "printer-state-reasons".to_string(),
+            "paused,media-low-warning".to_string(),
+        );
+
+        let mut dest = Destination {
+            name: "TestPrinter".to_string(),
+            instance: None,
+            is_default: false,
+            options,
+        };
+        assert!(dest.is_paused());
+
+        dest.options
+            .insert("printer-state-reasons".to_string(), "none".to_string());
+        assert!(!dest.is_paused());
+        assert_eq!(dest.state_message(), None);
+
+        dest.options.insert(
+            "printer-state-message".to_string(),
+            "Paper jam in tray 2".to_string(),
+        );
+        assert_eq!(dest.state_message(), Some(&"Paper jam in tray 2".to_string()));
+    }
+
+    #[test]
+    fn test_destination_bool_options() {
+        let dest = Destination::new("Office")
+            .with_option("printer-is-shared", "true")
+            .with_option("printer-is-temporary", "false")
+            .with_option("color-supported", "true");
+
+        assert!(dest.is_shared());
+        assert!(!dest.is_temporary());
+        assert!(dest.supports_color());
+        assert_eq!(dest.get_bool_option("printer-is-temporary"), Some(false));
+        assert_eq!(dest.get_bool_option("printer-is-accepting-jobs"), None);
+
+        let bare = Destination::new("Bare");
+        assert!(!bare.is_shared());
+        assert!(!bare.supports_color());
+    }
+
+    #[test]
+    fn test_destination_capabilities() {
+        let mut options = std::collections::HashMap::new();
+        options.insert(
+            "printer-type".to_string(),
+            (constants::PRINTER_COLOR | constants::PRINTER_DUPLEX).to_string(),
+        );
+
+        let dest = Destination {
+            name: "TestPrinter".to_string(),
+            instance: None,
+            is_default: false,
+            options,
+        };
+
+        let caps = dest.capabilities();
+        assert!(caps.is_color());
+        assert!(caps.is_duplex());
+        assert!(!caps.can_staple());
+    }
+
+    #[test]
+    fn test_apply_dest_event() {
+        let dest = |name: &str, info: &str| {
+            let mut options = std::collections::HashMap::new();
+            options.insert("printer-info".to_string(), info.to_string());
+            Destination {
+                name: name.to_string(),
+                instance: None,
+                is_default: false,
+                options,
+            }
+        };
+
+        let mut dests = Vec::new();
+        apply_dest_event(&mut dests, constants::DEST_FLAGS_NONE, &dest("A", "first"));
+        apply_dest_event(&mut dests, constants::DEST_FLAGS_NONE, &dest("B", "first"));
+        apply_dest_event(&mut dests, constants::DEST_FLAGS_NONE, &dest("A", "second"));
+        assert_eq!(dests.len(), 2);
+        assert_eq!(dests[0].info(), Some(&"second".to_string()));
+
+        apply_dest_event(&mut dests, constants::DEST_FLAGS_REMOVED, &dest("A", "second"));
+        assert_eq!(dests.len(), 1);
+        assert_eq!(dests[0].name, "B");
+
+        apply_dest_event(&mut dests, constants::DEST_FLAGS_REMOVED, &dest("C", "first"));
+        assert_eq!(dests.len(), 1);
+    }
+
+    #[test]
+    fn test_destination_builder() {
+        let dest = Destination::new("TestPrinter")
+            .with_option("printer-info", "Test Printer")
+            .with_option("printer-state", "4");
+
+        assert_eq!(dest.name, "TestPrinter");
+        assert_eq!(dest.instance, None);
+        assert!(!dest.is_default);
+        assert_eq!(dest.info(), Some(&"Test Printer".to_string()));
+        assert_eq!(dest.state(), PrinterState::Processing);
+        assert_eq!(Destination::default().name, "");
+    }
+
+    #[test]
+    fn test_destination_display() {
+        let dest = Destination::new("Office")
+            .with_option("printer-state", "3")
+            .with_option("printer-info", "Office Laser")
+            .with_option("printer-location", "Room 12");
+        assert_eq!(dest.to_string(), "Office (Idle) — Office Laser, Room 12");
+
+        let bare = Destination::new("Bare").with_option("printer-state", "5");
+        assert_eq!(bare.to_string(), "Bare (Stopped)");
+    }
+
+    #[test]
+    fn test_destination_summary() {
+        let mut dest = Destination::new("Office")
+            .with_option("printer-state", "3")
+            .with_option("printer-info", "Office Laser")
+            .with_option("printer-is-accepting-jobs", "true")
+            .with_option("printer-state-reasons", "toner-low");
+        dest.is_default = true;
+
+        assert_eq!(
+            dest.summary(),
+            "Office (Idle) [default]\n  Description: Office Laser\n  Accepting jobs: yes\n  Issues: toner-low"
+        );
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_destination_mock() {
+        let dest = Destination::mock("MockPrinter", [("printer-location", "Lab")]);
+        assert_eq!(dest.state(), PrinterState::Idle);
+        assert!(dest.is_accepting_jobs());
+        assert_eq!(dest.location(), Some(&"Lab".to_string()));
+
+        let stopped = Destination::mock("MockPrinter", [("printer-state", "5")]);
+        assert_eq!(stopped.state(), PrinterState::Stopped);
+    }
+
+    #[test]
+    fn test_set_option_value() {
+        let mut options = vec![("sides".to_string(), "one-sided".to_string())];
+        set_option_value(&mut options, "sides", "two-sided-long-edge");
+        set_option_value(&mut options, "media", "iso_a4_210x297mm");
+
+        assert_eq!(
+            options,
+            vec![
+                ("sides".to_string(), "two-sided-long-edge".to_string()),
+                ("media".to_string(), "iso_a4_210x297mm".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_full_name() {
+        assert_eq!(split_full_name("Office"), ("Office", None));
+        assert_eq!(split_full_name("Office/duplex"), ("Office", Some("duplex")));
+        assert_eq!(split_full_name("Office/"), ("Office", None));
+    }
+
     #[test]
     fn test_destination_with_instance() {
         let dest = Destination {
@@ -1058,5 +1569,170 @@ mod tests {
         assert_eq!(reasons.len(), 2);
         assert!(reasons.contains(&"media-tray-empty-error".to_string()));
         assert!(reasons.contains(&"toner-low-warning".to_string()));
+
+        let parsed = dest.parsed_state_reasons();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].keyword, "media-tray-empty");
+        assert_eq!(parsed[0].severity, Severity::Error);
+        assert_eq!(parsed[1].keyword, "toner-low");
+        assert_eq!(parsed[1].severity, Severity::Warning);
+
+        let idle = Destination::new("Idle").with_option("printer-state-reasons", "none");
+        assert!(idle.parsed_state_reasons().is_empty());
+    }
+
+    #[test]
+    fn test_raw_dest() {
+        let mut dest = Destination::new("Test").with_option("printer-state", "3");
+
+        let first = dest.as_ptr();
+        assert!(!first.is_null());
+        assert_eq!(unsafe { (*first.as_ptr()).num_options }, 1);
+
+        // Unchanged destinations, and equal clones, reuse the cached structure
+        assert_eq!(dest.as_ptr().as_ptr(), first.as_ptr());
+        assert_eq!(dest.clone().as_ptr().as_ptr(), first.as_ptr());
+
+        dest.options.insert("copies".to_string(), "2".to_string());
+        let rebuilt = dest.as_ptr();
+        assert_ne!(rebuilt.as_ptr(), first.as_ptr());
+        assert_eq!(unsafe { (*rebuilt.as_ptr()).num_options }, 2);
+        assert_eq!(unsafe { (*first.as_ptr()).num_options }, 1);
+
+        dest.options.insert("copies".to_string(), "3".to_string());
+        let changed = dest.as_ptr();
+        assert_ne!(changed.as_ptr(), rebuilt.as_ptr());
+        let copies = CString::new("copies").unwrap();
+        let value = unsafe {
+            CStr::from_ptr(bindings::cupsGetOption(
+                copies.as_ptr(),
+                (*changed.as_ptr()).num_options,
+                (*changed.as_ptr()).options,
+            ))
+        };
+        assert_eq!(value.to_str().unwrap(), "3");
+
+        assert!(Destination::new("Bad\0Name").as_ptr().is_null());
+    }
+
+    #[test]
+    fn test_empty_dest_list() {
+        let none_added = empty_dest_list(bindings::ipp_status_e_IPP_STATUS_ERROR_NOT_FOUND).unwrap();
+        assert_eq!(none_added.iter().count(), 0);
+        assert_eq!(empty_dest_list(bindings::ipp_status_e_IPP_STATUS_OK).unwrap().iter().count(), 0);
+
+        assert!(matches!(
+            empty_dest_list(bindings::ipp_status_e_IPP_STATUS_ERROR_SERVICE_UNAVAILABLE),
+            Err(Error::DestinationListFailed)
+        ));
+        assert!(matches!(
+            empty_dest_list(bindings::ipp_status_e_IPP_STATUS_ERROR_INTERNAL),
+            Err(Error::DestinationListFailed)
+        ));
+    }
+
+    #[test]
+    fn test_destinations_iter() {
+        let mut dests = Destinations::new();
+        assert_eq!(dests.iter().count(), 0);
+
+        dests.add_destination("First", None).unwrap();
+        dests.add_destination("Second", Some("draft")).unwrap();
+
+        let mut names: Vec<String> = dests.iter().map(|dest| dest.full_name()).collect();
+        names.sort();
+        assert_eq!(names, vec!["First".to_string(), "Second/draft".to_string()]);
+
+        let mut count = 0;
+        for _dest in &dests {
+            count += 1;
+        }
+        assert_eq!(count, dests.len());
+    }
+
+    #[test]
+    fn test_destinations_refs() {
+        let mut dests = Destinations::new();
+        assert_eq!(dests.refs().count(), 0);
+
+        dests.add_destination("First", None).unwrap();
+        dests.add_destination("Second", Some("draft")).unwrap();
+
+        let mut names: Vec<String> = dests.refs().map(|dest| dest.full_name()).collect();
+        names.sort();
+        assert_eq!(names, vec!["First".to_string(), "Second/draft".to_string()]);
+
+        let second = dests.refs().find(|dest| dest.name() == "Second").unwrap();
+        assert_eq!(second.instance().as_deref(), Some("draft"));
+        assert_eq!(second.to_destination().full_name(), "Second/draft");
+    }
+
+    #[test]
+    fn test_destination_uris() {
+        let dest = Destination::new("Office").with_option(
+            "printer-uri-supported",
+            "ipp://office.local/ipp/print, https://office.local/ipp/print,ipps://office.local/ipp/print",
+        );
+        assert_eq!(
+            dest.uris(),
+            vec![
+                "ipp://office.local/ipp/print",
+                "https://office.local/ipp/print",
+                "ipps://office.local/ipp/print",
+            ]
+        );
+        assert_eq!(dest.secure_uri().as_deref(), Some("ipps://office.local/ipp/print"));
+
+        let dest = Destination::new("Plain")
+            .with_option("printer-uri-supported", "ipp://localhost/printers/Plain");
+        assert_eq!(dest.uris().len(), 1);
+        assert_eq!(dest.secure_uri(), None);
+
+        assert!(Destination::new("Bare").uris().is_empty());
+    }
+
+    #[test]
+    fn test_destination_printer_uri() {
+        let dest = Destination::new("Office").with_option(
+            "printer-uri-supported",
+            "ipp://office.local/ipp/print,ipps://office.local/ipp/print",
+        );
+        assert_eq!(dest.printer_uri().unwrap(), "ipp://office.local/ipp/print");
+
+        let bare = Destination::new("Bare");
+        assert_eq!(bare.printer_uri().unwrap(), "ipp://localhost/printers/Bare");
+    }
+
+    #[test]
+    fn test_destination_equality() {
+        let mut options = std::collections::HashMap::new();
+        options.insert("printer-state".to_string(), "3".to_string());
+
+        let idle = Destination {
+            name: "Test".to_string(),
+            instance: None,
+            is_default: false,
+            options: options.clone(),
+        };
+
+        options.insert("printer-state".to_string(), "4".to_string());
+        let busy = Destination {
+            name: "Test".to_string(),
+            instance: None,
+            is_default: false,
+            options,
+        };
+
+        let instance = Destination {
+            instance: Some("draft".to_string()),
+            ..idle.clone()
+        };
+
+        assert_eq!(idle, busy);
+        assert_ne!(idle, instance);
+
+        let unique: std::collections::HashSet<Destination> =
+            vec![idle, busy, instance].into_iter().collect();
+        assert_eq!(unique.len(), 2);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,63 @@
+use super::Destination;
+use crate::bindings;
+use crate::error::{Error, Result};
+use crate::error_helpers::cups_error_to_our_error;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::path::{Path, PathBuf};
+use std::ptr;
+
+/// Size of the buffer CUPS writes the temporary PPD filename into
+const PPD_PATH_BUFFER_SIZE: usize = 1024;
+
+/// Legacy PPD (PostScript Printer Description) support
+///
+/// PPD files are deprecated in CUPS 2.x in favour of IPP attributes, and
+/// driverless printers have none, but many printer drivers still ship them.
+impl Destination {
+    /// Download the destination's PPD file to a temporary file
+    ///
+    /// Returns the path of the temporary copy, which the caller owns and
+    /// should delete with [`remove_ppd`] when done. Fails with
+    /// `Error::UnsupportedFeature` if the destination has no PPD.
+    pub fn get_ppd(&self) -> Result<PathBuf> {
+        let name_c = CString::new(self.name.as_str())?;
+        let mut modtime: bindings::time_t = 0;
+        let mut buffer = [0 as c_char; PPD_PATH_BUFFER_SIZE];
+
+        let status = unsafe {
+            bindings::cupsGetPPD3(
+                ptr::null_mut(), // Use CUPS_HTTP_DEFAULT
+                name_c.as_ptr(),
+                &mut modtime,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+            )
+        };
+
+        if status == bindings::http_status_e_HTTP_STATUS_NOT_FOUND as bindings::http_status_t {
+            return Err(Error::UnsupportedFeature(format!(
+                "Destination '{}' has no PPD file",
+                self.name
+            )));
+        }
+        if status != bindings::http_status_e_HTTP_STATUS_OK as bindings::http_status_t {
+            return Err(cups_error_to_our_error("Get PPD", Some(&self.name)));
+        }
+
+        let path = unsafe { CStr::from_ptr(buffer.as_ptr()) }
+            .to_string_lossy()
+            .into_owned();
+        if path.is_empty() {
+            return Err(cups_error_to_our_error("Get PPD", Some(&self.name)));
+        }
+
+        Ok(PathBuf::from(path))
+    }
+}
+
+/// Delete a temporary PPD file returned by [`Destination::get_ppd`]
+pub fn remove_ppd<P: AsRef<Path>>(path: P) -> Result<()> {
+    std::fs::remove_file(path)?;
+    Ok(())
+}
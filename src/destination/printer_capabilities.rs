@@ -0,0 +1,150 @@
+use crate::constants::*;
+
+/// Printer features decoded from the `printer-type` attribute
+///
+/// Wraps the `PRINTER_*` bits CUPS reports for each destination so callers
+/// don't have to mask them by hand. Use [`PrinterCapabilities::contains`] for
+/// bits without a dedicated accessor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrinterCapabilities {
+    bits: u32,
+}
+
+impl PrinterCapabilities {
+    /// Create capabilities from raw `printer-type` bits
+    pub fn from_bits(bits: u32) -> Self {
+        PrinterCapabilities { bits }
+    }
+
+    /// Parse a `printer-type` option value, treating invalid values as no bits set
+    pub fn from_printer_type(value: &str) -> Self {
+        Self::from_bits(value.trim().parse().unwrap_or(0))
+    }
+
+    /// Get the raw `printer-type` bits
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Check if all of the given `PRINTER_*` bits are set
+    pub fn contains(&self, flags: u32) -> bool {
+        self.bits & flags == flags
+    }
+
+    /// Check if the destination is a class of printers
+    pub fn is_class(&self) -> bool {
+        self.contains(PRINTER_CLASS)
+    }
+
+    /// Check if the destination is hosted on another server
+    pub fn is_remote(&self) -> bool {
+        self.contains(PRINTER_REMOTE)
+    }
+
+    /// Check if the destination was found through network discovery
+    pub fn is_discovered(&self) -> bool {
+        self.contains(PRINTER_DISCOVERED)
+    }
+
+    /// Check if the destination is a fax queue
+    pub fn is_fax(&self) -> bool {
+        self.contains(PRINTER_FAX)
+    }
+
+    /// Check if the destination is shared with other computers
+    pub fn is_shared(&self) -> bool {
+        !self.contains(PRINTER_NOT_SHARED)
+    }
+
+    /// Check if the printer can print in black and white
+    pub fn is_bw(&self) -> bool {
+        self.contains(PRINTER_BW)
+    }
+
+    /// Check if the printer can print in color
+    pub fn is_color(&self) -> bool {
+        self.contains(PRINTER_COLOR)
+    }
+
+    /// Check if the printer can print on both sides
+    pub fn is_duplex(&self) -> bool {
+        self.contains(PRINTER_DUPLEX)
+    }
+
+    /// Check if the printer can staple output
+    pub fn can_staple(&self) -> bool {
+        self.contains(PRINTER_STAPLE)
+    }
+
+    /// Check if the printer can produce copies in hardware
+    pub fn can_copy(&self) -> bool {
+        self.contains(PRINTER_COPIES)
+    }
+
+    /// Check if the printer can collate copies
+    pub fn can_collate(&self) -> bool {
+        self.contains(PRINTER_COLLATE)
+    }
+
+    /// Check if the printer can punch output
+    pub fn can_punch(&self) -> bool {
+        self.contains(PRINTER_PUNCH)
+    }
+
+    /// Check if the printer can cover output
+    pub fn can_cover(&self) -> bool {
+        self.contains(PRINTER_COVER)
+    }
+
+    /// Check if the printer can bind output
+    pub fn can_bind(&self) -> bool {
+        self.contains(PRINTER_BIND)
+    }
+
+    /// Check if the printer can sort output
+    pub fn can_sort(&self) -> bool {
+        self.contains(PRINTER_SORT)
+    }
+
+    /// Check if the printer supports custom media sizes
+    pub fn supports_variable_media(&self) -> bool {
+        self.contains(PRINTER_VARIABLE)
+    }
+
+    /// Check if the printer requires authentication to print
+    pub fn requires_authentication(&self) -> bool {
+        self.contains(PRINTER_AUTHENTICATED)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_printer_capabilities_from_printer_type() {
+        let caps = PrinterCapabilities::from_printer_type(
+            &(PRINTER_COLOR | PRINTER_DUPLEX | PRINTER_STAPLE | PRINTER_REMOTE).to_string(),
+        );
+
+        assert!(caps.is_color());
+        assert!(caps.is_duplex());
+        assert!(caps.can_staple());
+        assert!(caps.is_remote());
+        assert!(caps.is_shared());
+        assert!(!caps.is_class());
+        assert!(!caps.is_bw());
+        assert!(!caps.can_punch());
+        assert!(caps.contains(PRINTER_COLOR | PRINTER_DUPLEX));
+        assert!(!caps.contains(PRINTER_COLOR | PRINTER_BIND));
+    }
+
+    #[test]
+    fn test_printer_capabilities_invalid_value() {
+        let caps = PrinterCapabilities::from_printer_type("not-a-number");
+        assert_eq!(caps, PrinterCapabilities::default());
+        assert_eq!(caps.bits(), 0);
+        assert!(!caps.is_remote());
+    }
+}
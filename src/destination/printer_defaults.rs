@@ -0,0 +1,89 @@
+use crate::job::{ColorMode, DuplexMode, PrintQuality, Resolution};
+
+/// The printer's default job settings, read from its `*-default` attributes
+///
+/// Returned by [`Destination::defaults`](super::Destination::defaults) so a
+/// print dialog can pre-select its controls in one call. Each field is `None`
+/// when the printer doesn't report that default or reports a value this crate
+/// doesn't model.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PrinterDefaults {
+    /// Media keyword from `media-default`, e.g. `"iso_a4_210x297mm"`
+    pub media: Option<String>,
+    /// Duplex mode from `sides-default`
+    pub sides: Option<DuplexMode>,
+    /// Color mode from `print-color-mode-default`
+    pub color_mode: Option<ColorMode>,
+    /// Quality from `print-quality-default`
+    pub quality: Option<PrintQuality>,
+    /// Resolution from `printer-resolution-default`
+    pub resolution: Option<Resolution>,
+    /// Number of copies from `copies-default`
+    pub copies: Option<u32>,
+}
+
+impl PrinterDefaults {
+    /// Build defaults from the string values of the `*-default` attributes
+    pub(crate) fn from_values(
+        media: Option<String>,
+        sides: Option<String>,
+        color_mode: Option<String>,
+        quality: Option<String>,
+        copies: Option<String>,
+        resolution: Option<Resolution>,
+    ) -> Self {
+        PrinterDefaults {
+            media: media.filter(|m| !m.is_empty()),
+            sides: sides.as_deref().and_then(DuplexMode::from_cups_value),
+            color_mode: color_mode.as_deref().and_then(ColorMode::from_cups_value),
+            quality: quality.as_deref().and_then(PrintQuality::from_cups_value),
+            resolution,
+            copies: copies
+                .and_then(|c| c.trim().parse().ok())
+                .filter(|&c| c > 0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::job::ResolutionUnit;
+
+    #[test]
+    fn test_printer_defaults_from_values() {
+        let resolution = Resolution {
+            x: 600,
+            y: 600,
+            unit: ResolutionUnit::Dpi,
+        };
+        let defaults = PrinterDefaults::from_values(
+            Some("iso_a4_210x297mm".to_string()),
+            Some("two-sided-long-edge".to_string()),
+            Some("monochrome".to_string()),
+            Some("4".to_string()),
+            Some("1".to_string()),
+            Some(resolution),
+        );
+
+        assert_eq!(defaults.media.as_deref(), Some("iso_a4_210x297mm"));
+        assert_eq!(defaults.sides, Some(DuplexMode::TwoSidedPortrait));
+        assert_eq!(defaults.color_mode, Some(ColorMode::Monochrome));
+        assert_eq!(defaults.quality, Some(PrintQuality::Normal));
+        assert_eq!(defaults.resolution, Some(resolution));
+        assert_eq!(defaults.copies, Some(1));
+    }
+
+    #[test]
+    fn test_printer_defaults_missing() {
+        let defaults = PrinterDefaults::from_values(
+            None,
+            Some("sideways".to_string()),
+            None,
+            None,
+            Some("0".to_string()),
+            None,
+        );
+        assert_eq!(defaults, PrinterDefaults::default());
+    }
+}
@@ -1,5 +1,8 @@
+use std::sync::OnceLock;
+
 /// Represents the operational state of a printer
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PrinterState {
     /// Printer is idle and available for printing
     Idle,
@@ -12,29 +15,68 @@ pub enum PrinterState {
 }
 
 impl PrinterState {
-    /// Create a PrinterState from the CUPS state value
+    /// Create a PrinterState from the `printer-state` option string
+    ///
+    /// Values that are not numbers or not known states map to `Unknown`.
     pub fn from_cups_state(state: &str) -> Self {
-        match state {
-            "3" => PrinterState::Idle,
-            "4" => PrinterState::Processing,
-            "5" => PrinterState::Stopped,
+        match state.trim().parse::<i32>() {
+            Ok(code) => PrinterState::from_ipp_code(code),
+            Err(_) => PrinterState::Unknown,
+        }
+    }
+
+    /// Convert a CUPS `ipp_pstate_t` value
+    ///
+    /// The mapping uses the constants generated from the installed CUPS
+    /// headers rather than hard-coded state numbers.
+    pub fn from_ipp_code(code: i32) -> Self {
+        match code as u32 {
+            crate::bindings::ipp_pstate_e_IPP_PSTATE_IDLE => PrinterState::Idle,
+            crate::bindings::ipp_pstate_e_IPP_PSTATE_PROCESSING => PrinterState::Processing,
+            crate::bindings::ipp_pstate_e_IPP_PSTATE_STOPPED => PrinterState::Stopped,
             _ => PrinterState::Unknown,
         }
     }
 
+    /// Convert back to the CUPS `ipp_pstate_t` value (0 for `Unknown`)
+    pub fn to_ipp_code(&self) -> i32 {
+        match self {
+            PrinterState::Idle => crate::bindings::ipp_pstate_e_IPP_PSTATE_IDLE as i32,
+            PrinterState::Processing => crate::bindings::ipp_pstate_e_IPP_PSTATE_PROCESSING as i32,
+            PrinterState::Stopped => crate::bindings::ipp_pstate_e_IPP_PSTATE_STOPPED as i32,
+            PrinterState::Unknown => 0,
+        }
+    }
+
     /// Returns true if the printer is available for printing
     pub fn is_available(&self) -> bool {
         matches!(self, PrinterState::Idle | PrinterState::Processing)
     }
 
     /// Get the raw CUPS state value as a string
+    ///
+    /// The strings are formatted once from [`to_ipp_code`](Self::to_ipp_code),
+    /// so they follow the generated `ipp_pstate_e` constants.
     pub fn to_cups_value(&self) -> &'static str {
-        match self {
-            PrinterState::Idle => "3",
-            PrinterState::Processing => "4",
-            PrinterState::Stopped => "5",
-            PrinterState::Unknown => "0",
-        }
+        static VALUES: OnceLock<[String; 4]> = OnceLock::new();
+
+        let values = VALUES.get_or_init(|| {
+            [
+                PrinterState::Idle,
+                PrinterState::Processing,
+                PrinterState::Stopped,
+                PrinterState::Unknown,
+            ]
+            .map(|state| state.to_ipp_code().to_string())
+        });
+
+        let index = match self {
+            PrinterState::Idle => 0,
+            PrinterState::Processing => 1,
+            PrinterState::Stopped => 2,
+            PrinterState::Unknown => 3,
+        };
+        &values[index]
     }
 }
 
@@ -49,6 +91,56 @@ impl std::fmt::Display for PrinterState {
     }
 }
 
+/// How serious a `printer-state-reasons` entry is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Severity {
+    /// Informational, e.g. `toner-low-report`
+    Report,
+    /// The printer still works but needs attention, e.g. `toner-low-warning`
+    Warning,
+    /// The printer cannot print, e.g. `media-jam-error`
+    Error,
+}
+
+/// One entry of a printer's `printer-state-reasons`, split into keyword and severity
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StateReason {
+    /// The reason without its severity suffix, e.g. `toner-low`
+    pub keyword: String,
+    pub severity: Severity,
+}
+
+impl StateReason {
+    /// Parse a reason such as `media-jam-error`
+    ///
+    /// The `-report`, `-warning` and `-error` suffixes are stripped into
+    /// `severity`. Reasons without a suffix, such as `paused`, are errors as
+    /// required by RFC 8011. Returns `None` for `none` and empty strings.
+    pub fn parse(reason: &str) -> Option<Self> {
+        let reason = reason.trim();
+        if reason.is_empty() || reason == "none" {
+            return None;
+        }
+
+        let (keyword, severity) = if let Some(keyword) = reason.strip_suffix("-report") {
+            (keyword, Severity::Report)
+        } else if let Some(keyword) = reason.strip_suffix("-warning") {
+            (keyword, Severity::Warning)
+        } else if let Some(keyword) = reason.strip_suffix("-error") {
+            (keyword, Severity::Error)
+        } else {
+            (reason, Severity::Error)
+        };
+
+        Some(StateReason {
+            keyword: keyword.to_string(),
+            severity,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -59,6 +151,22 @@ mod tests {
         assert_eq!(PrinterState::from_cups_state("4"), PrinterState::Processing);
         assert_eq!(PrinterState::from_cups_state("5"), PrinterState::Stopped);
         assert_eq!(PrinterState::from_cups_state("unknown"), PrinterState::Unknown);
+        assert_eq!(PrinterState::from_cups_state(" 3 "), PrinterState::Idle);
+        assert_eq!(PrinterState::from_cups_state("7"), PrinterState::Unknown);
+    }
+
+    #[test]
+    fn test_printer_state_ipp_codes() {
+        for state in [PrinterState::Idle, PrinterState::Processing, PrinterState::Stopped] {
+            assert_eq!(PrinterState::from_ipp_code(state.to_ipp_code()), state);
+            assert_eq!(
+                PrinterState::from_cups_state(&state.to_ipp_code().to_string()),
+                state
+            );
+        }
+        assert_eq!(PrinterState::from_ipp_code(0), PrinterState::Unknown);
+        assert_eq!(PrinterState::from_ipp_code(-1), PrinterState::Unknown);
+        assert_eq!(PrinterState::Unknown.to_ipp_code(), 0);
     }
 
     #[test]
@@ -77,11 +185,39 @@ mod tests {
         assert_eq!(PrinterState::Unknown.to_string(), "Unknown");
     }
 
+    #[test]
+    fn test_state_reason_parse() {
+        let reason = StateReason::parse("toner-low-warning").unwrap();
+        assert_eq!(reason.keyword, "toner-low");
+        assert_eq!(reason.severity, Severity::Warning);
+
+        let reason = StateReason::parse("media-jam-error").unwrap();
+        assert_eq!(reason.keyword, "media-jam");
+        assert_eq!(reason.severity, Severity::Error);
+
+        let reason = StateReason::parse(" cups-insecure-filter-report ").unwrap();
+        assert_eq!(reason.keyword, "cups-insecure-filter");
+        assert_eq!(reason.severity, Severity::Report);
+
+        let reason = StateReason::parse("paused").unwrap();
+        assert_eq!(reason.keyword, "paused");
+        assert_eq!(reason.severity, Severity::Error);
+
+        assert_eq!(StateReason::parse("none"), None);
+        assert_eq!(StateReason::parse(""), None);
+        assert!(Severity::Report < Severity::Warning && Severity::Warning < Severity::Error);
+    }
+
     #[test]
     fn test_printer_state_cups_values() {
         assert_eq!(PrinterState::Idle.to_cups_value(), "3");
         assert_eq!(PrinterState::Processing.to_cups_value(), "4");
         assert_eq!(PrinterState::Stopped.to_cups_value(), "5");
         assert_eq!(PrinterState::Unknown.to_cups_value(), "0");
+
+        for state in [PrinterState::Idle, PrinterState::Processing, PrinterState::Stopped] {
+            assert_eq!(state.to_cups_value(), state.to_ipp_code().to_string());
+            assert_eq!(PrinterState::from_cups_state(state.to_cups_value()), state);
+        }
     }
 }
\ No newline at end of file
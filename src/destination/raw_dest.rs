@@ -0,0 +1,158 @@
+use super::Destination;
+use crate::bindings;
+use crate::error::Result;
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::ptr;
+use std::rc::Rc;
+
+/// Number of raw destinations kept per thread by [`RawDest::cached`]
+const CACHE_SIZE: usize = 8;
+
+thread_local! {
+    /// Raw destinations recently built on this thread, most recently used last
+    ///
+    /// `Destination`'s fields are public, so a cache inside it would break
+    /// struct-literal construction and could not see `options` being mutated.
+    /// Entries are instead checked against the destination's current fields,
+    /// so a changed destination misses and gets rebuilt.
+    static CACHE: RefCell<Vec<Rc<OwnedDest>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// An owned raw `cups_dest_t` built from a [`Destination`](super::Destination)
+///
+/// The pointer returned by [`RawDest::as_ptr`] is only valid while the guard
+/// is alive, so keep the guard in a local binding for as long as CUPS uses
+/// the pointer.
+///
+/// Building the structure adds every option through `cupsAddOption`, so
+/// [`Destination::as_ptr`](super::Destination::as_ptr) reuses one built
+/// recently on the same thread for an unchanged destination. Guards may
+/// therefore share a structure: never modify it through the pointer.
+///
+/// `RawDest` is neither `Send` nor `Sync`: CUPS takes a mutable `cups_dest_t`
+/// pointer and doesn't document its destination functions as safe to call
+/// concurrently on the same structure.
+pub struct RawDest {
+    inner: Option<Rc<OwnedDest>>,
+}
+
+/// The structure, its name and instance strings, and its options array,
+/// freed when the last guard and the cache have let go of it
+struct OwnedDest {
+    dest: *mut bindings::cups_dest_s,
+}
+
+impl RawDest {
+    /// Take ownership of a destination whose name and instance came from
+    /// `CString::into_raw` and whose options came from `cupsAddOption`
+    pub(crate) fn from_box(dest: Box<bindings::cups_dest_s>) -> Self {
+        RawDest {
+            inner: Some(Rc::new(OwnedDest {
+                dest: Box::into_raw(dest),
+            })),
+        }
+    }
+
+    /// A guard holding no destination, used when the name cannot be converted
+    pub(crate) fn null() -> Self {
+        RawDest { inner: None }
+    }
+
+    /// Reuse a structure built on this thread for `dest`, or `build` a new one
+    ///
+    /// A cached structure is only reused if its name, instance, default flag
+    /// and options all still match `dest`; the check reads the structure in
+    /// place and allocates nothing.
+    pub(crate) fn cached(
+        dest: &Destination,
+        build: impl FnOnce() -> Result<RawDest>,
+    ) -> Result<RawDest> {
+        let hit = CACHE.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            let index = cache.iter().position(|owned| owned.matches(dest))?;
+            let owned = cache.remove(index);
+            cache.push(Rc::clone(&owned));
+            Some(owned)
+        });
+        if let Some(owned) = hit {
+            return Ok(RawDest { inner: Some(owned) });
+        }
+
+        let raw = build()?;
+        // Options that cupsAddOption merged or skipped would never match
+        if let Some(owned) = raw.inner.as_ref().filter(|owned| owned.matches(dest)) {
+            CACHE.with(|cache| {
+                let mut cache = cache.borrow_mut();
+                if cache.len() == CACHE_SIZE {
+                    cache.remove(0);
+                }
+                cache.push(Rc::clone(owned));
+            });
+        }
+        Ok(raw)
+    }
+
+    /// Get the raw pointer to the cups_dest_t structure
+    ///
+    /// Returns a null pointer if the destination could not be built.
+    pub fn as_ptr(&self) -> *mut bindings::cups_dest_s {
+        self.inner
+            .as_ref()
+            .map_or(ptr::null_mut(), |owned| owned.dest)
+    }
+
+    /// Check if the destination could not be built
+    pub fn is_null(&self) -> bool {
+        self.inner.is_none()
+    }
+}
+
+impl OwnedDest {
+    /// Check that this structure still describes `dest` exactly
+    fn matches(&self, dest: &Destination) -> bool {
+        let raw = unsafe { &*self.dest };
+
+        let name = unsafe { CStr::from_ptr(raw.name) }.to_bytes();
+        let instance = if raw.instance.is_null() {
+            None
+        } else {
+            Some(unsafe { CStr::from_ptr(raw.instance) }.to_bytes())
+        };
+
+        if name != dest.name.as_bytes()
+            || instance != dest.instance.as_deref().map(str::as_bytes)
+            || (raw.is_default != 0) != dest.is_default
+            || raw.num_options as usize != dest.options.len()
+        {
+            return false;
+        }
+
+        (0..raw.num_options as usize).all(|i| {
+            let option = unsafe { &*raw.options.add(i) };
+            let name = unsafe { CStr::from_ptr(option.name) }.to_str();
+            let value = unsafe { CStr::from_ptr(option.value) }.to_bytes();
+
+            name.ok()
+                .and_then(|name| dest.options.get(name))
+                .is_some_and(|expected| expected.as_bytes() == value)
+        })
+    }
+}
+
+impl Drop for OwnedDest {
+    fn drop(&mut self) {
+        unsafe {
+            let dest_box = Box::from_raw(self.dest);
+            if !dest_box.name.is_null() {
+                let _ = CString::from_raw(dest_box.name);
+            }
+            if !dest_box.instance.is_null() {
+                let _ = CString::from_raw(dest_box.instance);
+            }
+            if !dest_box.options.is_null() {
+                bindings::cupsFreeOptions(dest_box.num_options, dest_box.options);
+            }
+        }
+    }
+}
@@ -1,6 +1,34 @@
+use crate::bindings;
 use std::ffi::NulError;
 use thiserror::Error;
 
+const IPP_FORBIDDEN: u16 = bindings::ipp_status_e_IPP_STATUS_ERROR_FORBIDDEN as u16;
+const IPP_NOT_AUTHENTICATED: u16 = bindings::ipp_status_e_IPP_STATUS_ERROR_NOT_AUTHENTICATED as u16;
+const IPP_NOT_AUTHORIZED: u16 = bindings::ipp_status_e_IPP_STATUS_ERROR_NOT_AUTHORIZED as u16;
+const IPP_NOT_POSSIBLE: u16 = bindings::ipp_status_e_IPP_STATUS_ERROR_NOT_POSSIBLE as u16;
+const IPP_TIMEOUT: u16 = bindings::ipp_status_e_IPP_STATUS_ERROR_TIMEOUT as u16;
+const IPP_GONE: u16 = bindings::ipp_status_e_IPP_STATUS_ERROR_GONE as u16;
+const IPP_REQUEST_ENTITY: u16 = bindings::ipp_status_e_IPP_STATUS_ERROR_REQUEST_ENTITY as u16;
+const IPP_DOCUMENT_FORMAT_NOT_SUPPORTED: u16 =
+    bindings::ipp_status_e_IPP_STATUS_ERROR_DOCUMENT_FORMAT_NOT_SUPPORTED as u16;
+const IPP_DOCUMENT_FORMAT_ERROR: u16 =
+    bindings::ipp_status_e_IPP_STATUS_ERROR_DOCUMENT_FORMAT_ERROR as u16;
+const IPP_DOCUMENT_ACCESS: u16 = bindings::ipp_status_e_IPP_STATUS_ERROR_DOCUMENT_ACCESS as u16;
+const IPP_SERVICE_UNAVAILABLE: u16 =
+    bindings::ipp_status_e_IPP_STATUS_ERROR_SERVICE_UNAVAILABLE as u16;
+const IPP_DEVICE: u16 = bindings::ipp_status_e_IPP_STATUS_ERROR_DEVICE as u16;
+const IPP_TEMPORARY: u16 = bindings::ipp_status_e_IPP_STATUS_ERROR_TEMPORARY as u16;
+const IPP_NOT_ACCEPTING_JOBS: u16 = bindings::ipp_status_e_IPP_STATUS_ERROR_NOT_ACCEPTING_JOBS as u16;
+const IPP_BUSY: u16 = bindings::ipp_status_e_IPP_STATUS_ERROR_BUSY as u16;
+const IPP_JOB_CANCELED: u16 = bindings::ipp_status_e_IPP_STATUS_ERROR_JOB_CANCELED as u16;
+const IPP_PRINTER_IS_DEACTIVATED: u16 =
+    bindings::ipp_status_e_IPP_STATUS_ERROR_PRINTER_IS_DEACTIVATED as u16;
+const IPP_TOO_MANY_JOBS: u16 = bindings::ipp_status_e_IPP_STATUS_ERROR_TOO_MANY_JOBS as u16;
+const IPP_TOO_MANY_DOCUMENTS: u16 = bindings::ipp_status_e_IPP_STATUS_ERROR_TOO_MANY_DOCUMENTS as u16;
+const IPP_AUTHENTICATION_CANCELED: u16 =
+    bindings::ipp_status_e_IPP_STATUS_ERROR_CUPS_AUTHENTICATION_CANCELED as u16;
+const IPP_PKI: u16 = bindings::ipp_status_e_IPP_STATUS_ERROR_CUPS_PKI as u16;
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("Failed to get destinations from CUPS server")]
@@ -18,6 +46,9 @@ pub enum Error {
     #[error("CUPS server error: {0}")]
     ServerError(String),
 
+    #[error("IPP error {code:#06x}: {message}")]
+    Ipp { code: u16, message: String },
+
     #[error("Invalid name containing null bytes: {0}")]
     InvalidName(String),
 
@@ -91,8 +122,21 @@ impl From<NulError> for Error {
 }
 
 impl Error {
+    /// Get the IPP status code reported by CUPS, if this error carries one
+    pub fn ipp_status_code(&self) -> Option<u16> {
+        match self {
+            Error::Ipp { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+
     pub fn is_recoverable(&self) -> bool {
         match self {
+            Error::Ipp { code, .. } => matches!(
+                *code,
+                IPP_TIMEOUT | IPP_SERVICE_UNAVAILABLE | IPP_DEVICE | IPP_TEMPORARY | IPP_BUSY
+            ),
+
             Error::ServerUnavailable
             | Error::NetworkError(_)
             | Error::Timeout
@@ -113,6 +157,29 @@ impl Error {
 
     pub fn error_category(&self) -> ErrorCategory {
         match self {
+            Error::Ipp { code, .. } => match *code {
+                IPP_TIMEOUT | IPP_SERVICE_UNAVAILABLE | IPP_TEMPORARY | IPP_BUSY => {
+                    ErrorCategory::Network
+                }
+                IPP_FORBIDDEN
+                | IPP_NOT_AUTHENTICATED
+                | IPP_NOT_AUTHORIZED
+                | IPP_AUTHENTICATION_CANCELED
+                | IPP_PKI => ErrorCategory::Authentication,
+                IPP_DEVICE | IPP_NOT_ACCEPTING_JOBS | IPP_PRINTER_IS_DEACTIVATED => {
+                    ErrorCategory::Printer
+                }
+                IPP_REQUEST_ENTITY
+                | IPP_DOCUMENT_FORMAT_NOT_SUPPORTED
+                | IPP_DOCUMENT_FORMAT_ERROR
+                | IPP_DOCUMENT_ACCESS => ErrorCategory::Document,
+                IPP_NOT_POSSIBLE
+                | IPP_GONE
+                | IPP_JOB_CANCELED
+                | IPP_TOO_MANY_JOBS
+                | IPP_TOO_MANY_DOCUMENTS => ErrorCategory::Job,
+                _ => ErrorCategory::General,
+            },
             Error::ServerUnavailable | Error::NetworkError(_) | Error::Timeout | Error::ConnectionFailed(_) => {
                 ErrorCategory::Network
             }
@@ -159,3 +226,47 @@ pub enum ErrorCategory {
     Configuration,
     General,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ipp_error(code: u16) -> Error {
+        Error::Ipp {
+            code,
+            message: "test".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_ipp_error_code() {
+        let error = ipp_error(IPP_NOT_POSSIBLE);
+        assert_eq!(error.ipp_status_code(), Some(0x0404));
+        assert_eq!(error.to_string(), "IPP error 0x0404: test");
+        assert_eq!(Error::Timeout.ipp_status_code(), None);
+    }
+
+    #[test]
+    fn test_ipp_error_classification() {
+        assert!(ipp_error(IPP_BUSY).is_recoverable());
+        assert!(ipp_error(IPP_SERVICE_UNAVAILABLE).is_recoverable());
+        assert!(!ipp_error(IPP_NOT_POSSIBLE).is_recoverable());
+        assert!(!ipp_error(IPP_FORBIDDEN).is_recoverable());
+
+        assert_eq!(ipp_error(IPP_TIMEOUT).error_category(), ErrorCategory::Network);
+        assert_eq!(
+            ipp_error(IPP_NOT_AUTHORIZED).error_category(),
+            ErrorCategory::Authentication
+        );
+        assert_eq!(
+            ipp_error(IPP_NOT_ACCEPTING_JOBS).error_category(),
+            ErrorCategory::Printer
+        );
+        assert_eq!(
+            ipp_error(IPP_DOCUMENT_FORMAT_NOT_SUPPORTED).error_category(),
+            ErrorCategory::Document
+        );
+        assert_eq!(ipp_error(IPP_NOT_POSSIBLE).error_category(), ErrorCategory::Job);
+        assert_eq!(ipp_error(0x0400).error_category(), ErrorCategory::General);
+    }
+}
@@ -23,13 +23,20 @@ pub fn cups_error_to_our_error(operation: &str, dest_name: Option<&str>) -> Erro
     match code {
         0 => Error::ServerUnavailable,
 
-        401 => Error::AuthenticationRequired(dest_name.unwrap_or("unknown").to_string()),
+        bindings::ipp_status_e_IPP_STATUS_ERROR_NOT_AUTHENTICATED => {
+            Error::AuthenticationRequired(dest_name.unwrap_or("unknown").to_string())
+        }
 
-        403 => Error::PermissionDenied(dest_name.unwrap_or("unknown").to_string()),
+        bindings::ipp_status_e_IPP_STATUS_ERROR_FORBIDDEN
+        | bindings::ipp_status_e_IPP_STATUS_ERROR_NOT_AUTHORIZED => {
+            Error::PermissionDenied(dest_name.unwrap_or("unknown").to_string())
+        }
 
-        404 => Error::DestinationNotFound(dest_name.unwrap_or("unknown").to_string()),
+        bindings::ipp_status_e_IPP_STATUS_ERROR_NOT_FOUND => {
+            Error::DestinationNotFound(dest_name.unwrap_or("unknown").to_string())
+        }
 
-        503 => {
+        bindings::ipp_status_e_IPP_STATUS_ERROR_NOT_ACCEPTING_JOBS => {
             Error::PrinterNotAccepting(dest_name.unwrap_or("unknown").to_string(), message.clone())
         }
 
@@ -41,22 +48,46 @@ pub fn cups_error_to_our_error(operation: &str, dest_name: Option<&str>) -> Erro
             } else if message.contains("network") || message.contains("connection") {
                 Error::NetworkError(message)
             } else {
-                Error::ServerError(format!("{} failed (code {}): {}", operation, code, message))
+                Error::Ipp {
+                    code: code as u16,
+                    message: format!("{} failed: {}", operation, message),
+                }
             }
         }
     }
 }
 
-pub fn validate_document_format(format: &str, dest_name: &str) -> Result<(), Error> {
-    let supported_formats = [
-        "application/pdf",
-        "application/postscript",
-        "text/plain",
-        "image/jpeg",
-        "image/png",
-    ];
-
-    if !supported_formats.contains(&format) {
+/// Formats accepted when the printer doesn't report `document-format-supported`
+const FALLBACK_DOCUMENT_FORMATS: [&str; 8] = [
+    "application/pdf",
+    "application/postscript",
+    "text/plain",
+    "image/jpeg",
+    "image/png",
+    "image/tiff",
+    "image/gif",
+    "image/urf",
+];
+
+/// Check a document format against the formats the printer reports
+///
+/// `supported` is the destination's `document-format-supported` list; when it
+/// is empty a small static list of common formats is used instead.
+pub fn validate_document_format(
+    format: &str,
+    dest_name: &str,
+    supported: &[String],
+) -> Result<(), Error> {
+    // Opaque and vendor formats are passed through to the printer untouched
+    let is_pass_through = format == "application/octet-stream" || format.starts_with("application/vnd.");
+
+    let is_supported = if supported.is_empty() {
+        FALLBACK_DOCUMENT_FORMATS.contains(&format)
+    } else {
+        supported.iter().any(|f| f == format)
+    };
+
+    if !is_pass_through && !is_supported {
         return Err(Error::InvalidFormat(
             format.to_string(),
             dest_name.to_string(),
@@ -75,3 +106,35 @@ pub fn check_document_size(size: usize, max_size: Option<usize>) -> Result<(), E
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_document_format() {
+        assert!(validate_document_format("application/pdf", "Test", &[]).is_ok());
+        assert!(validate_document_format("application/vnd.cups-raw", "Test", &[]).is_ok());
+        assert!(validate_document_format("application/vnd.zebra-zpl", "Test", &[]).is_ok());
+        assert!(validate_document_format("application/octet-stream", "Test", &[]).is_ok());
+        assert!(validate_document_format("image/tiff", "Test", &[]).is_ok());
+        assert!(validate_document_format("image/gif", "Test", &[]).is_ok());
+        assert!(validate_document_format("image/urf", "Test", &[]).is_ok());
+        assert!(matches!(
+            validate_document_format("application/x-unknown", "Test", &[]),
+            Err(Error::InvalidFormat(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_validate_document_format_against_printer() {
+        let supported = vec!["image/pwg-raster".to_string(), "image/urf".to_string()];
+
+        assert!(validate_document_format("image/pwg-raster", "Test", &supported).is_ok());
+        assert!(validate_document_format("application/vnd.cups-raw", "Test", &supported).is_ok());
+        assert!(matches!(
+            validate_document_format("application/pdf", "Test", &supported),
+            Err(Error::InvalidFormat(_, _))
+        ));
+    }
+}
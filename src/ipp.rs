@@ -9,19 +9,14 @@
 //! ## Creating and Sending an IPP Request
 //!
 //! ```no_run
-//! use cups_rs::{IppRequest, IppOperation, IppTag, IppValueTag, ConnectionFlags, get_default_destination};
+//! use cups_rs::{IppOperation, get_default_destination};
 //!
 //! let printer = get_default_destination().expect("No default printer");
-//! let connection = printer.connect(ConnectionFlags::Scheduler, Some(5000), None)
-//!     .expect("Failed to connect");
 //!
-//! let mut request = IppRequest::new(IppOperation::GetPrinterAttributes)
+//! // Connects to the scheduler and sets the printer-uri attribute
+//! let (request, connection) = printer.new_request(IppOperation::GetPrinterAttributes)
 //!     .expect("Failed to create request");
 //!
-//! request.add_string(IppTag::Operation, IppValueTag::Uri,
-//!                   "printer-uri", "ipp://localhost/printers/default")
-//!     .expect("Failed to add attribute");
-//!
 //! let response = request.send(&connection, connection.resource_path())
 //!     .expect("Failed to send request");
 //!
@@ -33,9 +28,14 @@
 use crate::bindings;
 use crate::connection::HttpConnection;
 use crate::error::{Error, Result};
+use crate::error_helpers::get_cups_error_details;
 use std::ffi::{CStr, CString};
 use std::marker::PhantomData;
 use std::ptr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Maximum number of attempts `send_into` makes before giving up on authentication
+const MAX_SEND_ATTEMPTS: usize = 4;
 
 /// IPP attribute group tags
 ///
@@ -67,6 +67,22 @@ impl From<IppTag> for bindings::ipp_tag_t {
     }
 }
 
+impl IppTag {
+    /// Convert a CUPS group tag, mapping unrecognized tags to `Zero`
+    pub fn from_code(code: bindings::ipp_tag_t) -> Self {
+        match code {
+            bindings::ipp_tag_e_IPP_TAG_OPERATION => IppTag::Operation,
+            bindings::ipp_tag_e_IPP_TAG_JOB => IppTag::Job,
+            bindings::ipp_tag_e_IPP_TAG_PRINTER => IppTag::Printer,
+            bindings::ipp_tag_e_IPP_TAG_SUBSCRIPTION => IppTag::Subscription,
+            bindings::ipp_tag_e_IPP_TAG_EVENT_NOTIFICATION => IppTag::EventNotification,
+            bindings::ipp_tag_e_IPP_TAG_DOCUMENT => IppTag::Document,
+            bindings::ipp_tag_e_IPP_TAG_UNSUPPORTED_GROUP => IppTag::UnsupportedGroup,
+            _ => IppTag::Zero,
+        }
+    }
+}
+
 /// IPP value tags
 ///
 /// These tags define the type of value an IPP attribute contains.
@@ -83,6 +99,16 @@ pub enum IppValueTag {
     Charset,
     Language,
     MimeType,
+    Date,
+    Resolution,
+    Range,
+    Collection,
+    TextWithLanguage,
+    NameWithLanguage,
+    UriScheme,
+    NoValue,
+    Unsupported,
+    Unknown,
 }
 
 impl From<IppValueTag> for bindings::ipp_tag_t {
@@ -99,8 +125,65 @@ impl From<IppValueTag> for bindings::ipp_tag_t {
             IppValueTag::Charset => bindings::ipp_tag_e_IPP_TAG_CHARSET,
             IppValueTag::Language => bindings::ipp_tag_e_IPP_TAG_LANGUAGE,
             IppValueTag::MimeType => bindings::ipp_tag_e_IPP_TAG_MIMETYPE,
+            IppValueTag::Date => bindings::ipp_tag_e_IPP_TAG_DATE,
+            IppValueTag::Resolution => bindings::ipp_tag_e_IPP_TAG_RESOLUTION,
+            IppValueTag::Range => bindings::ipp_tag_e_IPP_TAG_RANGE,
+            IppValueTag::Collection => bindings::ipp_tag_e_IPP_TAG_BEGIN_COLLECTION,
+            IppValueTag::TextWithLanguage => bindings::ipp_tag_e_IPP_TAG_TEXTLANG,
+            IppValueTag::NameWithLanguage => bindings::ipp_tag_e_IPP_TAG_NAMELANG,
+            IppValueTag::UriScheme => bindings::ipp_tag_e_IPP_TAG_URISCHEME,
+            IppValueTag::NoValue => bindings::ipp_tag_e_IPP_TAG_NOVALUE,
+            IppValueTag::Unsupported => bindings::ipp_tag_e_IPP_TAG_UNSUPPORTED_VALUE,
+            IppValueTag::Unknown => bindings::ipp_tag_e_IPP_TAG_UNKNOWN,
+        }
+    }
+}
+
+impl IppValueTag {
+    /// Convert a CUPS value tag, mapping unrecognized tags to `Unknown`
+    pub fn from_code(code: bindings::ipp_tag_t) -> Self {
+        match code {
+            bindings::ipp_tag_e_IPP_TAG_INTEGER => IppValueTag::Integer,
+            bindings::ipp_tag_e_IPP_TAG_BOOLEAN => IppValueTag::Boolean,
+            bindings::ipp_tag_e_IPP_TAG_ENUM => IppValueTag::Enum,
+            bindings::ipp_tag_e_IPP_TAG_STRING => IppValueTag::String,
+            bindings::ipp_tag_e_IPP_TAG_TEXT => IppValueTag::Text,
+            bindings::ipp_tag_e_IPP_TAG_NAME => IppValueTag::Name,
+            bindings::ipp_tag_e_IPP_TAG_KEYWORD => IppValueTag::Keyword,
+            bindings::ipp_tag_e_IPP_TAG_URI => IppValueTag::Uri,
+            bindings::ipp_tag_e_IPP_TAG_CHARSET => IppValueTag::Charset,
+            bindings::ipp_tag_e_IPP_TAG_LANGUAGE => IppValueTag::Language,
+            bindings::ipp_tag_e_IPP_TAG_MIMETYPE => IppValueTag::MimeType,
+            bindings::ipp_tag_e_IPP_TAG_DATE => IppValueTag::Date,
+            bindings::ipp_tag_e_IPP_TAG_RESOLUTION => IppValueTag::Resolution,
+            bindings::ipp_tag_e_IPP_TAG_RANGE => IppValueTag::Range,
+            bindings::ipp_tag_e_IPP_TAG_BEGIN_COLLECTION => IppValueTag::Collection,
+            bindings::ipp_tag_e_IPP_TAG_TEXTLANG => IppValueTag::TextWithLanguage,
+            bindings::ipp_tag_e_IPP_TAG_NAMELANG => IppValueTag::NameWithLanguage,
+            bindings::ipp_tag_e_IPP_TAG_URISCHEME => IppValueTag::UriScheme,
+            bindings::ipp_tag_e_IPP_TAG_NOVALUE => IppValueTag::NoValue,
+            bindings::ipp_tag_e_IPP_TAG_UNSUPPORTED_VALUE => IppValueTag::Unsupported,
+            _ => IppValueTag::Unknown,
         }
     }
+
+    /// Check if values with this tag are read with `IppAttribute::get_string`
+    pub fn is_string(&self) -> bool {
+        matches!(
+            self,
+            IppValueTag::String
+                | IppValueTag::Text
+                | IppValueTag::Name
+                | IppValueTag::Keyword
+                | IppValueTag::Uri
+                | IppValueTag::Charset
+                | IppValueTag::Language
+                | IppValueTag::MimeType
+                | IppValueTag::TextWithLanguage
+                | IppValueTag::NameWithLanguage
+                | IppValueTag::UriScheme
+        )
+    }
 }
 
 /// IPP operation codes
@@ -109,30 +192,76 @@ impl From<IppValueTag> for bindings::ipp_tag_t {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum IppOperation {
     PrintJob,
+    PrintUri,
     ValidateJob,
     CreateJob,
     SendDocument,
+    SendUri,
     CancelJob,
     GetJobAttributes,
     GetJobs,
     GetPrinterAttributes,
     PausePrinter,
     ResumePrinter,
+    PurgeJobs,
+    HoldJob,
+    ReleaseJob,
+    RestartJob,
+    SetJobAttributes,
+    SetPrinterAttributes,
+    EnablePrinter,
+    DisablePrinter,
+    CreatePrinterSubscriptions,
+    CancelSubscription,
+    GetNotifications,
+    CupsGetDefault,
+    CupsGetPrinters,
+    CupsAddModifyPrinter,
+    CupsDeletePrinter,
+    CupsMoveJob,
+    CupsAcceptJobs,
+    CupsRejectJobs,
 }
 
 impl From<IppOperation> for bindings::ipp_op_t {
     fn from(op: IppOperation) -> bindings::ipp_op_t {
         match op {
             IppOperation::PrintJob => bindings::ipp_op_e_IPP_OP_PRINT_JOB,
+            IppOperation::PrintUri => bindings::ipp_op_e_IPP_OP_PRINT_URI,
             IppOperation::ValidateJob => bindings::ipp_op_e_IPP_OP_VALIDATE_JOB,
             IppOperation::CreateJob => bindings::ipp_op_e_IPP_OP_CREATE_JOB,
             IppOperation::SendDocument => bindings::ipp_op_e_IPP_OP_SEND_DOCUMENT,
+            IppOperation::SendUri => bindings::ipp_op_e_IPP_OP_SEND_URI,
             IppOperation::CancelJob => bindings::ipp_op_e_IPP_OP_CANCEL_JOB,
             IppOperation::GetJobAttributes => bindings::ipp_op_e_IPP_OP_GET_JOB_ATTRIBUTES,
             IppOperation::GetJobs => bindings::ipp_op_e_IPP_OP_GET_JOBS,
             IppOperation::GetPrinterAttributes => bindings::ipp_op_e_IPP_OP_GET_PRINTER_ATTRIBUTES,
             IppOperation::PausePrinter => bindings::ipp_op_e_IPP_OP_PAUSE_PRINTER,
             IppOperation::ResumePrinter => bindings::ipp_op_e_IPP_OP_RESUME_PRINTER,
+            IppOperation::PurgeJobs => bindings::ipp_op_e_IPP_OP_PURGE_JOBS,
+            IppOperation::HoldJob => bindings::ipp_op_e_IPP_OP_HOLD_JOB,
+            IppOperation::ReleaseJob => bindings::ipp_op_e_IPP_OP_RELEASE_JOB,
+            IppOperation::RestartJob => bindings::ipp_op_e_IPP_OP_RESTART_JOB,
+            IppOperation::SetJobAttributes => bindings::ipp_op_e_IPP_OP_SET_JOB_ATTRIBUTES,
+            IppOperation::SetPrinterAttributes => {
+                bindings::ipp_op_e_IPP_OP_SET_PRINTER_ATTRIBUTES
+            }
+            IppOperation::EnablePrinter => bindings::ipp_op_e_IPP_OP_ENABLE_PRINTER,
+            IppOperation::DisablePrinter => bindings::ipp_op_e_IPP_OP_DISABLE_PRINTER,
+            IppOperation::CreatePrinterSubscriptions => {
+                bindings::ipp_op_e_IPP_OP_CREATE_PRINTER_SUBSCRIPTIONS
+            }
+            IppOperation::CancelSubscription => bindings::ipp_op_e_IPP_OP_CANCEL_SUBSCRIPTION,
+            IppOperation::GetNotifications => bindings::ipp_op_e_IPP_OP_GET_NOTIFICATIONS,
+            IppOperation::CupsGetDefault => bindings::ipp_op_e_IPP_OP_CUPS_GET_DEFAULT,
+            IppOperation::CupsGetPrinters => bindings::ipp_op_e_IPP_OP_CUPS_GET_PRINTERS,
+            IppOperation::CupsAddModifyPrinter => {
+                bindings::ipp_op_e_IPP_OP_CUPS_ADD_MODIFY_PRINTER
+            }
+            IppOperation::CupsDeletePrinter => bindings::ipp_op_e_IPP_OP_CUPS_DELETE_PRINTER,
+            IppOperation::CupsMoveJob => bindings::ipp_op_e_IPP_OP_CUPS_MOVE_JOB,
+            IppOperation::CupsAcceptJobs => bindings::ipp_op_e_IPP_OP_CUPS_ACCEPT_JOBS,
+            IppOperation::CupsRejectJobs => bindings::ipp_op_e_IPP_OP_CUPS_REJECT_JOBS,
         }
     }
 }
@@ -160,6 +289,8 @@ pub enum IppStatus {
     ErrorPrinterIsDeactivated,
     ErrorTooManyJobs,
     ErrorInternalError,
+    /// A status code without a dedicated variant, kept as its raw number
+    Other(u16),
 }
 
 impl IppStatus {
@@ -197,15 +328,76 @@ impl IppStatus {
             }
             bindings::ipp_status_e_IPP_STATUS_ERROR_TOO_MANY_JOBS => IppStatus::ErrorTooManyJobs,
             bindings::ipp_status_e_IPP_STATUS_ERROR_INTERNAL => IppStatus::ErrorInternalError,
-            _ => IppStatus::ErrorInternalError,
+            _ => u16::try_from(code)
+                .map(IppStatus::Other)
+                .unwrap_or(IppStatus::ErrorInternalError),
         }
     }
 
+    /// Get the raw IPP status code, e.g. `0x0406` for `ErrorNotFound`
+    pub fn as_code(&self) -> u16 {
+        let code = match self {
+            IppStatus::Ok => bindings::ipp_status_e_IPP_STATUS_OK,
+            IppStatus::OkIgnoredOrSubstituted => {
+                bindings::ipp_status_e_IPP_STATUS_OK_IGNORED_OR_SUBSTITUTED
+            }
+            IppStatus::OkConflicting => bindings::ipp_status_e_IPP_STATUS_OK_CONFLICTING,
+            IppStatus::ErrorBadRequest => bindings::ipp_status_e_IPP_STATUS_ERROR_BAD_REQUEST,
+            IppStatus::ErrorForbidden => bindings::ipp_status_e_IPP_STATUS_ERROR_FORBIDDEN,
+            IppStatus::ErrorNotAuthenticated => {
+                bindings::ipp_status_e_IPP_STATUS_ERROR_NOT_AUTHENTICATED
+            }
+            IppStatus::ErrorNotAuthorized => {
+                bindings::ipp_status_e_IPP_STATUS_ERROR_NOT_AUTHORIZED
+            }
+            IppStatus::ErrorNotPossible => bindings::ipp_status_e_IPP_STATUS_ERROR_NOT_POSSIBLE,
+            IppStatus::ErrorTimeout => bindings::ipp_status_e_IPP_STATUS_ERROR_TIMEOUT,
+            IppStatus::ErrorNotFound => bindings::ipp_status_e_IPP_STATUS_ERROR_NOT_FOUND,
+            IppStatus::ErrorGone => bindings::ipp_status_e_IPP_STATUS_ERROR_GONE,
+            IppStatus::ErrorRequestEntity => {
+                bindings::ipp_status_e_IPP_STATUS_ERROR_REQUEST_ENTITY
+            }
+            IppStatus::ErrorRequestValue => bindings::ipp_status_e_IPP_STATUS_ERROR_REQUEST_VALUE,
+            IppStatus::ErrorDocumentFormatNotSupported => {
+                bindings::ipp_status_e_IPP_STATUS_ERROR_DOCUMENT_FORMAT_NOT_SUPPORTED
+            }
+            IppStatus::ErrorConflicting => bindings::ipp_status_e_IPP_STATUS_ERROR_CONFLICTING,
+            IppStatus::ErrorPrinterIsDeactivated => {
+                bindings::ipp_status_e_IPP_STATUS_ERROR_PRINTER_IS_DEACTIVATED
+            }
+            IppStatus::ErrorTooManyJobs => bindings::ipp_status_e_IPP_STATUS_ERROR_TOO_MANY_JOBS,
+            IppStatus::ErrorInternalError => bindings::ipp_status_e_IPP_STATUS_ERROR_INTERNAL,
+            IppStatus::Other(code) => return *code,
+        };
+        code as u16
+    }
+
+    /// Check if the status is a client error (`0x0400`-`0x04FF`)
+    pub fn is_client_error(&self) -> bool {
+        (0x0400..=0x04FF).contains(&self.as_code())
+    }
+
+    /// Check if the status is a server error (`0x0500`-`0x05FF`)
+    pub fn is_server_error(&self) -> bool {
+        (0x0500..=0x05FF).contains(&self.as_code())
+    }
+
     pub fn is_successful(&self) -> bool {
-        matches!(
-            self,
-            IppStatus::Ok | IppStatus::OkIgnoredOrSubstituted | IppStatus::OkConflicting
-        )
+        self.as_code() < 0x0100
+    }
+}
+
+impl From<IppStatus> for Error {
+    fn from(status: IppStatus) -> Self {
+        let code = status.as_code();
+        let name = unsafe { bindings::ippErrorString(code as bindings::ipp_status_t) };
+        let message = if name.is_null() {
+            format!("{:?}", status)
+        } else {
+            unsafe { CStr::from_ptr(name).to_string_lossy().into_owned() }
+        };
+
+        Error::Ipp { code, message }
     }
 }
 
@@ -333,6 +525,99 @@ impl IppRequest {
         }
     }
 
+    /// Add a rangeOfInteger attribute, e.g. a single `page-ranges` entry
+    pub fn add_range(&mut self, group: IppTag, name: &str, lower: i32, upper: i32) -> Result<()> {
+        let name_c = CString::new(name)?;
+
+        let attr = unsafe {
+            bindings::ippAddRange(self.ipp, group.into(), name_c.as_ptr(), lower, upper)
+        };
+
+        if attr.is_null() {
+            Err(Error::UnsupportedFeature(format!(
+                "Failed to add range attribute '{}'",
+                name
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Add a rangeOfInteger attribute with multiple `(lower, upper)` values
+    pub fn add_ranges(&mut self, group: IppTag, name: &str, ranges: &[(i32, i32)]) -> Result<()> {
+        let name_c = CString::new(name)?;
+        let lowers: Vec<::std::os::raw::c_int> = ranges.iter().map(|&(lower, _)| lower).collect();
+        let uppers: Vec<::std::os::raw::c_int> = ranges.iter().map(|&(_, upper)| upper).collect();
+
+        let attr = unsafe {
+            bindings::ippAddRanges(
+                self.ipp,
+                group.into(),
+                name_c.as_ptr(),
+                ranges.len() as i32,
+                lowers.as_ptr(),
+                uppers.as_ptr(),
+            )
+        };
+
+        if attr.is_null() {
+            Err(Error::UnsupportedFeature(format!(
+                "Failed to add range array attribute '{}'",
+                name
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Add an octetString attribute holding arbitrary bytes
+    ///
+    /// Used for binary values such as `job-password` or vendor extensions.
+    /// IPP limits octet strings to 1023 bytes.
+    pub fn add_octet_string(&mut self, group: IppTag, name: &str, bytes: &[u8]) -> Result<()> {
+        let name_c = CString::new(name)?;
+
+        let attr = unsafe {
+            bindings::ippAddOctetString(
+                self.ipp,
+                group.into(),
+                name_c.as_ptr(),
+                bytes.as_ptr() as *const ::std::os::raw::c_void,
+                bytes.len() as i32,
+            )
+        };
+
+        if attr.is_null() {
+            Err(Error::UnsupportedFeature(format!(
+                "Failed to add octet string attribute '{}'",
+                name
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Add a dateTime attribute, e.g. `job-hold-until-time`
+    pub fn add_date(&mut self, group: IppTag, name: &str, value: SystemTime) -> Result<()> {
+        let name_c = CString::new(name)?;
+
+        let attr = unsafe {
+            // ippTimeToDate returns a buffer that is reused on the next call,
+            // ippAddDate copies it before that can happen
+            let date = bindings::ippTimeToDate(system_time_to_unix(value) as bindings::time_t);
+            bindings::ippAddDate(self.ipp, group.into(), name_c.as_ptr(), date)
+        };
+
+        if attr.is_null() {
+            Err(Error::UnsupportedFeature(format!(
+                "Failed to add date attribute '{}'",
+                name
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
     /// Add multiple string attributes
     pub fn add_strings(
         &mut self,
@@ -372,24 +657,126 @@ impl IppRequest {
     }
 
     /// Send this request and receive a response
-    pub fn send(&self, connection: &HttpConnection, resource: &str) -> Result<IppResponse> {
+    ///
+    /// The request is consumed because `cupsDoRequest` frees it. Use
+    /// [`IppRequest::send_cloned`] to keep the request for another send.
+    pub fn send(self, connection: &HttpConnection, resource: &str) -> Result<IppResponse> {
+        self.send_with_http(connection.as_ptr(), resource)
+    }
+
+    /// Send a copy of this request, keeping the original for reuse
+    pub fn send_cloned(&self, connection: &HttpConnection, resource: &str) -> Result<IppResponse> {
+        self.duplicate()?.send(connection, resource)
+    }
+
+    /// Send this request without consuming or copying it, for repeated polling
+    ///
+    /// Unlike [`IppRequest::send_cloned`], which copies every attribute into a
+    /// new request before each send, the request is written to the connection
+    /// as-is with `cupsSendRequest` and can be sent again immediately. The
+    /// previous response in `response` is freed before sending, so a polling
+    /// loop holds at most one response at a time.
+    ///
+    /// Like `cupsDoRequest`, a request the server answers with 401
+    /// Unauthorized or 426 Upgrade Required is sent again once CUPS has
+    /// obtained credentials (see [`crate::auth`]) or enabled TLS. If the
+    /// password callback cancels, this fails with `Error::AuthenticationRequired`.
+    ///
+    /// Run `cargo bench --bench send_into` to compare it with `send_cloned`
+    /// against your own server.
+    ///
+    /// ```no_run
+    /// # use cups_rs::{IppOperation, IppResponse, IppTag, IppValueTag, get_default_destination};
+    /// # let printer = get_default_destination()?;
+    /// let (mut request, connection) = printer.new_request(IppOperation::GetPrinterAttributes)?;
+    /// request.add_string(IppTag::Operation, IppValueTag::Keyword, "requested-attributes", "printer-state")?;
+    ///
+    /// let mut response: Option<IppResponse> = None;
+    /// for _ in 0..10 {
+    ///     let state = request.send_into(&connection, connection.resource_path(), &mut response)?;
+    ///     println!("{:?}", state.find_attribute("printer-state", None).map(|a| a.get_integer(0)));
+    ///     std::thread::sleep(std::time::Duration::from_secs(1));
+    /// }
+    /// # Ok::<(), cups_rs::Error>(())
+    /// ```
+    pub fn send_into<'a>(
+        &self,
+        connection: &HttpConnection,
+        resource: &str,
+        response: &'a mut Option<IppResponse>,
+    ) -> Result<&'a IppResponse> {
+        let http = connection.checked_ptr()?;
         let resource_c = CString::new(resource)?;
+        *response = None;
 
-        // Note: cupsDoRequest frees the request, so we need to create a copy
-        let request_copy = unsafe { bindings::ippNew() };
-        if request_copy.is_null() {
-            return Err(Error::UnsupportedFeature(
-                "Failed to copy IPP request".to_string(),
-            ));
-        }
+        // cupsGetResponse prepares credentials or TLS after a 401 or 426 and
+        // returns no response; send again as cupsDoRequest does
+        for _ in 0..MAX_SEND_ATTEMPTS {
+            let status = unsafe {
+                bindings::cupsSendRequest(
+                    http,
+                    self.ipp,
+                    resource_c.as_ptr(),
+                    bindings::ippLength(self.ipp),
+                )
+            };
+            if status
+                == bindings::http_status_e_HTTP_STATUS_CUPS_AUTHORIZATION_CANCELED
+                    as bindings::http_status_t
+            {
+                return Err(Error::AuthenticationRequired(resource.to_string()));
+            }
+            if status != bindings::http_status_e_HTTP_STATUS_CONTINUE as bindings::http_status_t {
+                return Err(Error::ServerError(format!(
+                    "Failed to send IPP request (HTTP status {})",
+                    status
+                )));
+            }
 
-        unsafe {
-            bindings::ippCopyAttributes(request_copy, self.ipp, 0, None, ptr::null_mut());
+            let ipp = unsafe { bindings::cupsGetResponse(http, resource_c.as_ptr()) };
+            if !ipp.is_null() {
+                return Ok(response.insert(IppResponse {
+                    ipp,
+                    _phantom: PhantomData,
+                }));
+            }
+
+            let http_status = unsafe { bindings::httpGetStatus(http) };
+            let retry = http_status
+                == bindings::http_status_e_HTTP_STATUS_UNAUTHORIZED as bindings::http_status_t
+                || http_status
+                    == bindings::http_status_e_HTTP_STATUS_UPGRADE_REQUIRED
+                        as bindings::http_status_t;
+            if !retry {
+                return Err(Error::ServerError(
+                    "No response received from server".to_string(),
+                ));
+            }
+
+            let (code, _) = get_cups_error_details();
+            if code == bindings::ipp_status_e_IPP_STATUS_ERROR_CUPS_AUTHENTICATION_CANCELED {
+                return Err(Error::AuthenticationRequired(resource.to_string()));
+            }
         }
 
-        let response = unsafe {
-            bindings::cupsDoRequest(connection.as_ptr(), request_copy, resource_c.as_ptr())
-        };
+        Err(Error::AuthenticationFailed(format!(
+            "Server kept refusing the request to {}",
+            resource
+        )))
+    }
+
+    /// Send this request over a raw connection, or the default CUPS server when `http` is null
+    pub(crate) fn send_with_http(
+        mut self,
+        http: *mut bindings::_http_s,
+        resource: &str,
+    ) -> Result<IppResponse> {
+        let resource_c = CString::new(resource)?;
+
+        // cupsDoRequest frees the request, so Drop must not free it again
+        let request = std::mem::replace(&mut self.ipp, ptr::null_mut());
+
+        let response = unsafe { bindings::cupsDoRequest(http, request, resource_c.as_ptr()) };
 
         if response.is_null() {
             Err(Error::ServerError(
@@ -402,6 +789,30 @@ impl IppRequest {
             })
         }
     }
+
+    /// Copy the message header and all attributes into a new request
+    fn duplicate(&self) -> Result<IppRequest> {
+        let copy = unsafe { bindings::ippNew() };
+        if copy.is_null() {
+            return Err(Error::UnsupportedFeature(
+                "Failed to copy IPP request".to_string(),
+            ));
+        }
+
+        unsafe {
+            let mut minor = 0;
+            let major = bindings::ippGetVersion(self.ipp, &mut minor);
+            bindings::ippSetVersion(copy, major, minor);
+            bindings::ippSetOperation(copy, bindings::ippGetOperation(self.ipp));
+            bindings::ippSetRequestId(copy, bindings::ippGetRequestId(self.ipp));
+            bindings::ippCopyAttributes(copy, self.ipp, 0, None, ptr::null_mut());
+        }
+
+        Ok(IppRequest {
+            ipp: copy,
+            _phantom: PhantomData,
+        })
+    }
 }
 
 impl Drop for IppRequest {
@@ -457,6 +868,31 @@ impl IppResponse {
         self.status().is_successful()
     }
 
+    /// Get the server's `status-message` explaining the status, if any
+    pub fn status_message(&self) -> Option<String> {
+        self.find_attribute("status-message", None)
+            .and_then(|attr| attr.get_string(0))
+    }
+
+    /// Turn a non-successful response into an [`Error::Ipp`]
+    ///
+    /// The error carries the response's `status-message` when the server sent
+    /// one, otherwise the name of the status code.
+    pub fn into_result(self) -> Result<IppResponse> {
+        let status = self.status();
+        if status.is_successful() {
+            return Ok(self);
+        }
+
+        match self.status_message() {
+            Some(message) => Err(Error::Ipp {
+                code: status.as_code(),
+                message,
+            }),
+            None => Err(status.into()),
+        }
+    }
+
     /// Find an attribute by name
     pub fn find_attribute(&self, name: &str, group: Option<IppTag>) -> Option<IppAttribute> {
         let name_c = match CString::new(name) {
@@ -489,6 +925,40 @@ impl IppResponse {
 
         attributes
     }
+
+    /// Get the attributes belonging to one group, e.g. only `IppTag::Printer`
+    pub fn attributes_in_group(&self, group: IppTag) -> Vec<IppAttribute> {
+        self.attributes()
+            .into_iter()
+            .filter(|attr| attr.group_tag() == group)
+            .collect()
+    }
+
+    /// Get the attributes partitioned into groups in message order
+    ///
+    /// A group may appear more than once: a Get-Jobs response has one
+    /// `IppTag::Job` group per job. The separators CUPS places between
+    /// repeated groups are not included.
+    pub fn groups(&self) -> Vec<(IppTag, Vec<IppAttribute>)> {
+        let mut groups: Vec<(IppTag, Vec<IppAttribute>)> = Vec::new();
+        let mut separated = true;
+
+        for attr in self.attributes() {
+            let group = attr.group_tag();
+            if group == IppTag::Zero {
+                separated = true;
+                continue;
+            }
+
+            match groups.last_mut() {
+                Some((tag, attrs)) if *tag == group && !separated => attrs.push(attr),
+                _ => groups.push((group, vec![attr])),
+            }
+            separated = false;
+        }
+
+        groups
+    }
 }
 
 impl Drop for IppResponse {
@@ -512,6 +982,15 @@ pub struct IppAttribute {
 }
 
 impl IppAttribute {
+    /// Wrap an attribute pointer owned by an IPP message or `cups_dinfo_t`
+    pub(crate) fn from_raw(attr: *mut bindings::_ipp_attribute_s) -> Option<Self> {
+        if attr.is_null() {
+            None
+        } else {
+            Some(IppAttribute { attr })
+        }
+    }
+
     /// Get the attribute name
     pub fn name(&self) -> Option<String> {
         unsafe {
@@ -524,6 +1003,16 @@ impl IppAttribute {
         }
     }
 
+    /// Get the type of the attribute's values
+    pub fn value_tag(&self) -> IppValueTag {
+        IppValueTag::from_code(unsafe { bindings::ippGetValueTag(self.attr) })
+    }
+
+    /// Get the group the attribute belongs to
+    pub fn group_tag(&self) -> IppTag {
+        IppTag::from_code(unsafe { bindings::ippGetGroupTag(self.attr) })
+    }
+
     /// Get the number of values
     pub fn count(&self) -> usize {
         unsafe { bindings::ippGetCount(self.attr) as usize }
@@ -550,6 +1039,98 @@ impl IppAttribute {
     pub fn get_boolean(&self, index: usize) -> bool {
         unsafe { bindings::ippGetBoolean(self.attr, index as i32) != 0 }
     }
+
+    /// Get a value formatted as a string according to its value tag
+    ///
+    /// Strings are returned as-is, integers and enums as decimal numbers,
+    /// booleans as `"true"`/`"false"` and ranges as `"lower-upper"`. Returns
+    /// `None` for other value types or an out-of-bounds index.
+    pub fn value_as_string(&self, index: usize) -> Option<String> {
+        if index >= self.count() {
+            return None;
+        }
+
+        let tag = self.value_tag();
+        match tag {
+            IppValueTag::Integer | IppValueTag::Enum => Some(self.get_integer(index).to_string()),
+            IppValueTag::Boolean => Some(self.get_boolean(index).to_string()),
+            IppValueTag::Range => self
+                .get_range(index)
+                .map(|(lower, upper)| format!("{}-{}", lower, upper)),
+            _ if tag.is_string() => self.get_string(index),
+            _ => None,
+        }
+    }
+
+    /// Get a dateTime value
+    ///
+    /// The RFC 2579 date, including its UTC offset, is converted to an absolute
+    /// point in time. Returns `None` if the index is out of bounds or the
+    /// attribute does not hold dates.
+    pub fn get_date(&self, index: usize) -> Option<SystemTime> {
+        if index >= self.count() || self.value_tag() != IppValueTag::Date {
+            return None;
+        }
+
+        let date = unsafe { bindings::ippGetDate(self.attr, index as i32) };
+        if date.is_null() {
+            return None;
+        }
+
+        let seconds = unsafe { bindings::ippDateToTime(date) };
+        Some(unix_to_system_time(seconds as i64))
+    }
+
+    /// Get an octetString value as raw bytes
+    ///
+    /// Returns `None` if the index is out of bounds or the attribute does not
+    /// hold octet strings.
+    pub fn get_octet_string(&self, index: usize) -> Option<Vec<u8>> {
+        if index >= self.count() || self.value_tag() != IppValueTag::String {
+            return None;
+        }
+
+        let mut len: ::std::os::raw::c_int = 0;
+        let data = unsafe { bindings::ippGetOctetString(self.attr, index as i32, &mut len) };
+        if data.is_null() || len <= 0 {
+            return Some(Vec::new());
+        }
+
+        Some(unsafe { std::slice::from_raw_parts(data as *const u8, len as usize) }.to_vec())
+    }
+
+    /// Get a rangeOfInteger value as `(lower, upper)`
+    ///
+    /// Returns `None` if the index is out of bounds or the attribute does not
+    /// hold ranges.
+    pub fn get_range(&self, index: usize) -> Option<(i32, i32)> {
+        if index >= self.count() {
+            return None;
+        }
+
+        if self.value_tag() != IppValueTag::Range {
+            return None;
+        }
+
+        let mut upper: ::std::os::raw::c_int = 0;
+        let lower = unsafe { bindings::ippGetRange(self.attr, index as i32, &mut upper) };
+        Some((lower, upper))
+    }
+}
+
+fn system_time_to_unix(time: SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(after) => after.as_secs() as i64,
+        Err(before) => -(before.duration().as_secs() as i64),
+    }
+}
+
+fn unix_to_system_time(seconds: i64) -> SystemTime {
+    if seconds >= 0 {
+        UNIX_EPOCH + Duration::from_secs(seconds as u64)
+    } else {
+        UNIX_EPOCH - Duration::from_secs(seconds.unsigned_abs())
+    }
 }
 
 #[cfg(test)]
@@ -588,6 +1169,241 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_ipp_add_range() {
+        let mut request = IppRequest::new(IppOperation::CreateJob).unwrap();
+        let result = request.add_range(IppTag::Job, "page-ranges", 1, 5);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_ipp_add_ranges() {
+        let mut request = IppRequest::new(IppOperation::CreateJob).unwrap();
+        let result = request.add_ranges(IppTag::Job, "page-ranges", &[(1, 4), (7, 7), (10, 12)]);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_ipp_octet_string_round_trip() {
+        let mut request = IppRequest::new(IppOperation::CreateJob).unwrap();
+        request
+            .add_octet_string(IppTag::Operation, "job-password", &[0x31, 0x00, 0xff])
+            .unwrap();
+        request
+            .add_integer(IppTag::Operation, IppValueTag::Integer, "job-id", 7)
+            .unwrap();
+
+        let response = IppResponse {
+            ipp: std::mem::replace(&mut request.ipp, ptr::null_mut()),
+            _phantom: PhantomData,
+        };
+
+        let attr = response
+            .find_attribute("job-password", Some(IppTag::Operation))
+            .unwrap();
+        assert_eq!(attr.value_tag(), IppValueTag::String);
+        assert_eq!(attr.get_octet_string(0), Some(vec![0x31, 0x00, 0xff]));
+        assert_eq!(attr.get_octet_string(1), None);
+
+        let job_id = response
+            .find_attribute("job-id", Some(IppTag::Operation))
+            .unwrap();
+        assert_eq!(job_id.get_octet_string(0), None);
+    }
+
+    #[test]
+    fn test_ipp_request_duplicate() {
+        let mut request = IppRequest::new(IppOperation::GetJobs).unwrap();
+        request
+            .add_integer(IppTag::Operation, IppValueTag::Integer, "limit", 10)
+            .unwrap();
+
+        let copy = request.duplicate().unwrap();
+        assert_ne!(copy.as_ptr(), request.as_ptr());
+        unsafe {
+            assert_eq!(
+                bindings::ippGetOperation(copy.as_ptr()),
+                bindings::ippGetOperation(request.as_ptr())
+            );
+            assert_eq!(
+                bindings::ippGetRequestId(copy.as_ptr()),
+                bindings::ippGetRequestId(request.as_ptr())
+            );
+        }
+    }
+
+    #[test]
+    fn test_ipp_response_groups() {
+        let mut request = IppRequest::new(IppOperation::GetJobs).unwrap();
+        request
+            .add_integer(IppTag::Job, IppValueTag::Integer, "job-id", 1)
+            .unwrap();
+        unsafe {
+            bindings::ippAddSeparator(request.as_ptr());
+        }
+        request
+            .add_integer(IppTag::Job, IppValueTag::Integer, "job-id", 2)
+            .unwrap();
+        request
+            .add_string(IppTag::Printer, IppValueTag::Keyword, "printer-state-reasons", "none")
+            .unwrap();
+
+        let response = IppResponse {
+            ipp: std::mem::replace(&mut request.ipp, ptr::null_mut()),
+            _phantom: PhantomData,
+        };
+
+        let tags: Vec<IppTag> = response.groups().iter().map(|(tag, _)| *tag).collect();
+        assert_eq!(
+            tags,
+            vec![IppTag::Operation, IppTag::Job, IppTag::Job, IppTag::Printer]
+        );
+
+        let jobs = response.attributes_in_group(IppTag::Job);
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[1].get_integer(0), 2);
+    }
+
+    #[test]
+    fn test_ipp_attribute_value_as_string() {
+        let mut request = IppRequest::new(IppOperation::GetPrinterAttributes).unwrap();
+        request
+            .add_string(IppTag::Printer, IppValueTag::Keyword, "media-source", "tray-1")
+            .unwrap();
+        request
+            .add_integer(IppTag::Printer, IppValueTag::Enum, "finishings", 4)
+            .unwrap();
+        request
+            .add_boolean(IppTag::Printer, "color-supported", true)
+            .unwrap();
+        request
+            .add_range(IppTag::Printer, "copies-supported", 1, 99)
+            .unwrap();
+
+        let response = IppResponse {
+            ipp: std::mem::replace(&mut request.ipp, ptr::null_mut()),
+            _phantom: PhantomData,
+        };
+
+        let value = |name: &str| {
+            response
+                .find_attribute(name, Some(IppTag::Printer))
+                .and_then(|attr| attr.value_as_string(0))
+        };
+        assert_eq!(value("media-source").as_deref(), Some("tray-1"));
+        assert_eq!(value("finishings").as_deref(), Some("4"));
+        assert_eq!(value("color-supported").as_deref(), Some("true"));
+        assert_eq!(value("copies-supported").as_deref(), Some("1-99"));
+
+        let attr = response
+            .find_attribute("media-source", Some(IppTag::Printer))
+            .unwrap();
+        assert_eq!(attr.value_as_string(1), None);
+    }
+
+    #[test]
+    fn test_ipp_status_into_error() {
+        let error = Error::from(IppStatus::ErrorNotPossible);
+        assert_eq!(error.ipp_status_code(), Some(0x0404));
+
+        let error = Error::from(IppStatus::Other(0x0507));
+        assert!(error.is_recoverable());
+    }
+
+    #[test]
+    fn test_ipp_response_into_result() {
+        let mut request = IppRequest::new(IppOperation::GetJobs).unwrap();
+        let response = IppResponse {
+            ipp: std::mem::replace(&mut request.ipp, ptr::null_mut()),
+            _phantom: PhantomData,
+        };
+        unsafe {
+            bindings::ippSetStatusCode(
+                response.as_ptr(),
+                bindings::ipp_status_e_IPP_STATUS_ERROR_NOT_FOUND,
+            );
+        }
+
+        match response.into_result() {
+            Err(Error::Ipp { code, .. }) => assert_eq!(code, 0x0406),
+            other => panic!("expected an IPP error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_ipp_add_date() {
+        let mut request = IppRequest::new(IppOperation::CreateJob).unwrap();
+        let result = request.add_date(IppTag::Job, "job-hold-until-time", SystemTime::now());
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_unix_time_conversion() {
+        let time = UNIX_EPOCH + Duration::from_secs(1640995200);
+        assert_eq!(system_time_to_unix(time), 1640995200);
+        assert_eq!(unix_to_system_time(1640995200), time);
+
+        let before_epoch = UNIX_EPOCH - Duration::from_secs(3600);
+        assert_eq!(system_time_to_unix(before_epoch), -3600);
+        assert_eq!(unix_to_system_time(-3600), before_epoch);
+    }
+
+    #[test]
+    fn test_ipp_value_tag_round_trip() {
+        let tags = [
+            IppValueTag::Integer,
+            IppValueTag::Boolean,
+            IppValueTag::Enum,
+            IppValueTag::Keyword,
+            IppValueTag::Date,
+            IppValueTag::Resolution,
+            IppValueTag::Range,
+            IppValueTag::Collection,
+            IppValueTag::NoValue,
+        ];
+        for tag in tags {
+            assert_eq!(IppValueTag::from_code(tag.into()), tag);
+        }
+
+        assert!(IppValueTag::Keyword.is_string());
+        assert!(IppValueTag::NameWithLanguage.is_string());
+        assert!(!IppValueTag::Enum.is_string());
+        assert!(!IppValueTag::Range.is_string());
+    }
+
+    #[test]
+    fn test_ipp_group_tag_round_trip() {
+        let tags = [IppTag::Operation, IppTag::Job, IppTag::Printer, IppTag::Document];
+        for tag in tags {
+            assert_eq!(IppTag::from_code(tag.into()), tag);
+        }
+        assert_eq!(
+            IppTag::from_code(bindings::ipp_tag_e_IPP_TAG_INTEGER),
+            IppTag::Zero
+        );
+    }
+
+    #[test]
+    fn test_ipp_admin_operations() {
+        let operations = [
+            (IppOperation::HoldJob, bindings::ipp_op_e_IPP_OP_HOLD_JOB),
+            (IppOperation::ReleaseJob, bindings::ipp_op_e_IPP_OP_RELEASE_JOB),
+            (IppOperation::EnablePrinter, bindings::ipp_op_e_IPP_OP_ENABLE_PRINTER),
+            (IppOperation::CupsGetPrinters, bindings::ipp_op_e_IPP_OP_CUPS_GET_PRINTERS),
+            (IppOperation::PrintUri, bindings::ipp_op_e_IPP_OP_PRINT_URI),
+            (IppOperation::SendUri, bindings::ipp_op_e_IPP_OP_SEND_URI),
+            (IppOperation::CupsAcceptJobs, bindings::ipp_op_e_IPP_OP_CUPS_ACCEPT_JOBS),
+            (IppOperation::CupsRejectJobs, bindings::ipp_op_e_IPP_OP_CUPS_REJECT_JOBS),
+        ];
+        for (operation, code) in operations {
+            let op: bindings::ipp_op_t = operation.into();
+            assert_eq!(op, code);
+
+            let request = IppRequest::new(operation).unwrap();
+            assert_eq!(unsafe { bindings::ippGetOperation(request.as_ptr()) }, code);
+        }
+    }
+
     #[test]
     fn test_ipp_status() {
         assert!(IppStatus::Ok.is_successful());
@@ -595,4 +1411,27 @@ mod tests {
         assert!(!IppStatus::ErrorBadRequest.is_successful());
         assert!(!IppStatus::ErrorNotFound.is_successful());
     }
+
+    #[test]
+    fn test_ipp_status_codes() {
+        assert_eq!(IppStatus::Ok.as_code(), 0x0000);
+        assert_eq!(IppStatus::ErrorNotFound.as_code(), 0x0406);
+        assert_eq!(IppStatus::ErrorInternalError.as_code(), 0x0500);
+
+        let busy = IppStatus::from_code(bindings::ipp_status_e_IPP_STATUS_ERROR_BUSY);
+        assert_eq!(busy, IppStatus::Other(0x0507));
+        assert_eq!(busy.as_code(), 0x0507);
+
+        for status in [IppStatus::Ok, IppStatus::ErrorForbidden, IppStatus::ErrorTooManyJobs] {
+            assert_eq!(IppStatus::from_code(status.as_code() as bindings::ipp_status_t), status);
+        }
+
+        assert!(IppStatus::ErrorBadRequest.is_client_error());
+        assert!(!IppStatus::ErrorBadRequest.is_server_error());
+        assert!(IppStatus::ErrorInternalError.is_server_error());
+        assert!(busy.is_server_error());
+        assert!(IppStatus::Other(0x0007).is_successful());
+        assert!(!IppStatus::Ok.is_client_error());
+        assert!(!IppStatus::Ok.is_server_error());
+    }
 }
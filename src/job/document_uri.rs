@@ -0,0 +1,97 @@
+use super::Job;
+use crate::bindings;
+use crate::config::get_user;
+use crate::destination::Destination;
+use crate::error::{Error, Result};
+use crate::ipp::{IppOperation, IppRequest, IppResponse, IppTag, IppValueTag};
+use std::ptr;
+
+/// Print a document the server fetches itself from `doc_uri`
+///
+/// Issues a Print-URI request so large files already reachable by the server,
+/// e.g. `file:///var/spool/share/report.pdf` or `http://intranet/report.pdf`,
+/// are not uploaded through the client. Many CUPS servers disable Print-URI
+/// for security reasons; in that case, or when the URI scheme is not
+/// allowed, this fails with `Error::UnsupportedFeature`.
+pub fn create_job_with_document_uri(
+    dest: &Destination,
+    title: &str,
+    doc_uri: &str,
+    format: &str,
+) -> Result<Job> {
+    if !dest.is_accepting_jobs() {
+        return Err(Error::PrinterNotAccepting(
+            dest.name.clone(),
+            "Printer is not accepting new jobs".to_string(),
+        ));
+    }
+
+    let (mut request, connection) = dest.new_request(IppOperation::PrintUri)?;
+    request.add_string(
+        IppTag::Operation,
+        IppValueTag::Name,
+        "requesting-user-name",
+        &get_user(),
+    )?;
+    request.add_string(IppTag::Operation, IppValueTag::Name, "job-name", title)?;
+    add_document_attributes(&mut request, doc_uri, format)?;
+
+    let response = check_uri_response(request.send(&connection, "/")?, doc_uri)?;
+
+    let job_id = response
+        .find_attribute("job-id", Some(IppTag::Job))
+        .map(|attr| attr.get_integer(0))
+        .ok_or_else(|| Error::JobCreationFailed("Server did not return a job ID".to_string()))?;
+
+    Ok(Job::new(job_id, dest.name.clone(), title.to_string()))
+}
+
+impl Job {
+    /// Add a document the server fetches itself from `doc_uri` to this job
+    ///
+    /// Issues a Send-URI request; the URI counterpart of
+    /// [`Job::submit_data`]. Set `last_document` on the final document so the
+    /// job starts printing. Fails with `Error::UnsupportedFeature` when the
+    /// server does not allow fetching documents by URI.
+    pub fn submit_uri(&self, doc_uri: &str, format: &str, last_document: bool) -> Result<()> {
+        let dest = self.destination_on(ptr::null_mut())?;
+        let (mut request, connection) = dest.new_request(IppOperation::SendUri)?;
+        request.add_integer(IppTag::Operation, IppValueTag::Integer, "job-id", self.id)?;
+        request.add_string(
+            IppTag::Operation,
+            IppValueTag::Name,
+            "requesting-user-name",
+            &get_user(),
+        )?;
+        request.add_boolean(IppTag::Operation, "last-document", last_document)?;
+        add_document_attributes(&mut request, doc_uri, format)?;
+
+        check_uri_response(request.send(&connection, "/")?, doc_uri)?;
+        Ok(())
+    }
+}
+
+fn add_document_attributes(request: &mut IppRequest, doc_uri: &str, format: &str) -> Result<()> {
+    request.add_string(IppTag::Operation, IppValueTag::Uri, "document-uri", doc_uri)?;
+    request.add_string(
+        IppTag::Operation,
+        IppValueTag::MimeType,
+        "document-format",
+        format,
+    )
+}
+
+/// Report servers that refuse to fetch documents as an unsupported feature
+fn check_uri_response(response: IppResponse, doc_uri: &str) -> Result<IppResponse> {
+    let code = i32::from(response.status().as_code());
+    if code == bindings::ipp_status_e_IPP_STATUS_ERROR_OPERATION_NOT_SUPPORTED
+        || code == bindings::ipp_status_e_IPP_STATUS_ERROR_URI_SCHEME
+    {
+        return Err(Error::UnsupportedFeature(format!(
+            "Server does not allow printing documents by URI ('{}')",
+            doc_uri
+        )));
+    }
+
+    response.into_result()
+}
@@ -1,36 +1,42 @@
 use super::Job;
+use super::management::job_uri;
+use super::status::JobStatus;
 use crate::bindings;
+use crate::connection::HttpConnection;
 use crate::error::{Error, Result};
-use std::ffi::CString;
+use crate::error_helpers::get_cups_error_details;
+use crate::ipp::{IppOperation, IppRequest, IppTag, IppValueTag};
+use crate::options::encode_options_with_group;
 use std::ptr;
+use std::time::{Duration, Instant};
+
+const INITIAL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+const MAX_POLL_INTERVAL: Duration = Duration::from_secs(2);
 
 impl Job {
     pub fn close(&self) -> Result<()> {
-        let dest = crate::get_destination(&self.dest_name)?;
-        let dest_info = dest.get_detailed_info(ptr::null_mut())?;
-        let dest_ptr = dest.as_ptr();
+        self.close_on(ptr::null_mut())
+    }
+
+    /// Close the job over an existing connection
+    pub fn close_with_connection(&self, connection: &HttpConnection) -> Result<()> {
+        self.close_on(connection.checked_ptr()?)
+    }
+
+    fn close_on(&self, http: *mut bindings::_http_s) -> Result<()> {
+        let dest = self.destination_on(http)?;
+        let raw_dest = dest.as_ptr();
+        let dest_info = dest.get_detailed_info_for(http, &raw_dest)?;
+        let dest_ptr = raw_dest.as_ptr();
 
         if dest_ptr.is_null() {
             return Err(Error::NullPointer);
         }
 
         let status = unsafe {
-            bindings::cupsCloseDestJob(ptr::null_mut(), dest_ptr, dest_info.as_ptr(), self.id)
+            bindings::cupsCloseDestJob(http, dest_ptr, dest_info.as_ptr(), self.id)
         };
 
-        unsafe {
-            let dest_box = Box::from_raw(dest_ptr);
-            if !dest_box.name.is_null() {
-                let _ = CString::from_raw(dest_box.name);
-            }
-            if !dest_box.instance.is_null() {
-                let _ = CString::from_raw(dest_box.instance);
-            }
-            if !dest_box.options.is_null() {
-                bindings::cupsFreeOptions(dest_box.num_options, dest_box.options);
-            }
-        }
-
         if status == bindings::ipp_status_e_IPP_STATUS_OK as bindings::ipp_status_t {
             Ok(())
         } else {
@@ -52,27 +58,24 @@ impl Job {
     }
 
     pub fn cancel(&self) -> Result<()> {
-        let dest = crate::get_destination(&self.dest_name)?;
-        let dest_ptr = dest.as_ptr();
+        self.cancel_on(ptr::null_mut())
+    }
+
+    /// Cancel the job over an existing connection
+    pub fn cancel_with_connection(&self, connection: &HttpConnection) -> Result<()> {
+        self.cancel_on(connection.checked_ptr()?)
+    }
+
+    fn cancel_on(&self, http: *mut bindings::_http_s) -> Result<()> {
+        let dest = self.destination_on(http)?;
+        let raw_dest = dest.as_ptr();
+        let dest_ptr = raw_dest.as_ptr();
 
         if dest_ptr.is_null() {
             return Err(Error::NullPointer);
         }
 
-        let status = unsafe { bindings::cupsCancelDestJob(ptr::null_mut(), dest_ptr, self.id) };
-
-        unsafe {
-            let dest_box = Box::from_raw(dest_ptr);
-            if !dest_box.name.is_null() {
-                let _ = CString::from_raw(dest_box.name);
-            }
-            if !dest_box.instance.is_null() {
-                let _ = CString::from_raw(dest_box.instance);
-            }
-            if !dest_box.options.is_null() {
-                bindings::cupsFreeOptions(dest_box.num_options, dest_box.options);
-            }
-        }
+        let status = unsafe { bindings::cupsCancelDestJob(http, dest_ptr, self.id) };
 
         if status == bindings::ipp_status_e_IPP_STATUS_OK as bindings::ipp_status_t {
             Ok(())
@@ -93,4 +96,88 @@ impl Job {
             )))
         }
     }
+
+    /// Change the attributes of a job that has not started printing
+    ///
+    /// Issues a Set-Job-Attributes request with the given option/value pairs,
+    /// e.g. `("job-priority", "90")` or `("job-hold-until", "night")`. Values are
+    /// encoded the same way as print options.
+    ///
+    /// # Returns
+    /// - `Ok(())`: The job was updated
+    /// - `Err(Error::JobManagementFailed)`: The server refused the change, e.g.
+    ///   because the job is already processing
+    pub fn set_attributes(&self, options: &[(String, String)]) -> Result<()> {
+        let request = self.set_attributes_request(options)?;
+        let response = request.send_with_http(ptr::null_mut(), "/jobs")?;
+
+        if response.is_successful() {
+            Ok(())
+        } else {
+            let (_, message) = get_cups_error_details();
+            Err(Error::JobManagementFailed(format!(
+                "Failed to set attributes of job {}: {}",
+                self.id, message
+            )))
+        }
+    }
+
+    /// Build the Set-Job-Attributes request, addressing the job by `job-uri`
+    ///
+    /// The job URI doesn't involve the destination name, which may carry an
+    /// instance (`Office/duplex`) that is not part of the queue's URI.
+    pub(super) fn set_attributes_request(&self, options: &[(String, String)]) -> Result<IppRequest> {
+        if options.is_empty() {
+            return Err(Error::ConfigurationError("No job attributes to set".to_string()));
+        }
+
+        let mut request = IppRequest::new(IppOperation::SetJobAttributes)?;
+        request.add_string(IppTag::Operation, IppValueTag::Uri, "job-uri", &job_uri(self.id))?;
+        request.add_string(
+            IppTag::Operation,
+            IppValueTag::Name,
+            "requesting-user-name",
+            &crate::config::get_user(),
+        )?;
+        encode_options_with_group(request.as_ptr(), options, bindings::ipp_tag_e_IPP_TAG_JOB)?;
+
+        Ok(request)
+    }
+
+    /// Block until the job reaches a terminal state
+    ///
+    /// Polls the job status, doubling the poll interval after each check up to
+    /// a two second cap, until the job is completed, canceled or aborted.
+    ///
+    /// # Arguments
+    /// - `timeout`: Maximum time to wait, or None to wait indefinitely
+    ///
+    /// # Returns
+    /// - `Ok(JobStatus)`: The terminal status the job reached
+    /// - `Err(Error::Timeout)`: The timeout elapsed first
+    pub fn wait_for_completion(&self, timeout: Option<Duration>) -> Result<JobStatus> {
+        let deadline = timeout.map(|t| Instant::now() + t);
+        let mut interval = INITIAL_POLL_INTERVAL;
+
+        loop {
+            let status = super::get_job_info(self.id)?.status;
+            if status.is_terminal() {
+                return Ok(status);
+            }
+
+            let sleep_for = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    if remaining.is_zero() {
+                        return Err(Error::Timeout);
+                    }
+                    interval.min(remaining)
+                }
+                None => interval,
+            };
+
+            std::thread::sleep(sleep_for);
+            interval = (interval * 2).min(MAX_POLL_INTERVAL);
+        }
+    }
 }
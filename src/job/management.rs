@@ -1,7 +1,13 @@
-use super::status::{JobInfo, JobStatus};
+use super::{Job, JobQuery};
+use super::status::{JobFilter, JobInfo, JobStatus};
 use crate::bindings;
+use crate::connection::{HttpConnection, assemble_uri, uri_resource};
 use crate::constants::WHICHJOBS_ALL;
+use crate::destination::split_full_name;
 use crate::error::{Error, Result};
+use crate::error_helpers::get_cups_error_details;
+use crate::ipp::{IppAttribute, IppOperation, IppRequest, IppTag, IppValueTag};
+use std::collections::HashMap;
 use std::ffi::CString;
 use std::ptr;
 
@@ -9,26 +15,14 @@ pub fn cancel_job(job_id: i32) -> Result<()> {
     let destinations = crate::get_all_destinations()?;
 
     for dest in destinations {
-        let dest_ptr = dest.as_ptr();
+        let raw_dest = dest.as_ptr();
+        let dest_ptr = raw_dest.as_ptr();
         if dest_ptr.is_null() {
             continue;
         }
 
         let status = unsafe { bindings::cupsCancelDestJob(ptr::null_mut(), dest_ptr, job_id) };
 
-        unsafe {
-            let dest_box = Box::from_raw(dest_ptr);
-            if !dest_box.name.is_null() {
-                let _ = CString::from_raw(dest_box.name);
-            }
-            if !dest_box.instance.is_null() {
-                let _ = CString::from_raw(dest_box.instance);
-            }
-            if !dest_box.options.is_null() {
-                bindings::cupsFreeOptions(dest_box.num_options, dest_box.options);
-            }
-        }
-
         if status == bindings::ipp_status_e_IPP_STATUS_OK as bindings::ipp_status_t {
             return Ok(());
         }
@@ -40,19 +34,209 @@ pub fn cancel_job(job_id: i32) -> Result<()> {
     )))
 }
 
-pub fn get_jobs(dest_name: Option<&str>) -> Result<Vec<JobInfo>> {
-    get_jobs_with_filter(dest_name, WHICHJOBS_ALL)
+/// Cancel every job queued on a destination
+///
+/// Issues a single Purge-Jobs request instead of cancelling jobs one by one.
+/// CUPS does not report how many jobs were affected.
+///
+/// # Arguments
+/// - `dest_name`: Destination whose queue should be cleared
+/// - `purge`: If true, job history and files are removed as well; otherwise
+///   jobs are only cancelled
+///
+/// # Returns
+/// - `Ok(())`: Queue cleared successfully
+/// - `Err(Error::JobManagementFailed)`: The server rejected the request
+pub fn cancel_all_jobs(dest_name: &str, purge: bool) -> Result<()> {
+    let mut request = IppRequest::new(IppOperation::PurgeJobs)?;
+    request.add_string(
+        IppTag::Operation,
+        IppValueTag::Uri,
+        "printer-uri",
+        &printer_uri(dest_name)?,
+    )?;
+    request.add_string(
+        IppTag::Operation,
+        IppValueTag::Name,
+        "requesting-user-name",
+        &crate::config::get_user(),
+    )?;
+    request.add_boolean(IppTag::Operation, "purge-jobs", purge)?;
+
+    let response = request.send_with_http(ptr::null_mut(), "/admin/")?;
+
+    if response.is_successful() {
+        Ok(())
+    } else {
+        let (_, message) = get_cups_error_details();
+        Err(Error::JobManagementFailed(format!(
+            "Failed to cancel jobs on '{}': {}",
+            dest_name, message
+        )))
+    }
+}
+
+/// Move a job from one destination to another
+///
+/// Issues a CUPS-Move-Job request so a job queued on an unavailable printer
+/// can be printed elsewhere.
+///
+/// # Arguments
+/// - `job_id`: ID of the job to move
+/// - `from_dest`: Destination the job is currently queued on
+/// - `to_dest`: Destination to move the job to
+///
+/// # Returns
+/// - `Ok(())`: Job moved successfully
+/// - `Err(Error::JobManagementFailed)`: The server rejected the move
+pub fn move_job(job_id: i32, from_dest: &str, to_dest: &str) -> Result<()> {
+    let mut request = IppRequest::new(IppOperation::CupsMoveJob)?;
+    request.add_string(
+        IppTag::Operation,
+        IppValueTag::Uri,
+        "printer-uri",
+        &printer_uri(from_dest)?,
+    )?;
+    request.add_integer(IppTag::Operation, IppValueTag::Integer, "job-id", job_id)?;
+    request.add_string(
+        IppTag::Operation,
+        IppValueTag::Name,
+        "requesting-user-name",
+        &crate::config::get_user(),
+    )?;
+    request.add_string(
+        IppTag::Job,
+        IppValueTag::Uri,
+        "job-printer-uri",
+        &printer_uri(to_dest)?,
+    )?;
+
+    let response = request.send_with_http(ptr::null_mut(), "/jobs")?;
+
+    if response.is_successful() {
+        Ok(())
+    } else {
+        let (_, message) = get_cups_error_details();
+        Err(Error::JobManagementFailed(format!(
+            "Failed to move job {} from '{}' to '{}': {}",
+            job_id, from_dest, to_dest, message
+        )))
+    }
+}
+
+/// Build the scheduler URI for a destination on the current CUPS server
+///
+/// The queue name is percent-escaped, so names containing `%` or non-ASCII
+/// characters still produce a valid URI. When a `Destination` is at hand,
+/// `Destination::printer_uri` is preferred as it uses the printer's own URI.
+pub(crate) fn printer_uri(dest_name: &str) -> Result<String> {
+    assemble_uri("ipp", "localhost", 0, &format!("/printers/{}", dest_name))
+}
+
+/// Build the scheduler URI for a job on the current CUPS server
+pub(super) fn job_uri(job_id: i32) -> String {
+    format!("ipp://localhost/jobs/{}", job_id)
+}
+
+/// List all jobs, or with `Some(limit)` only the most recent `limit`
+///
+/// See [`get_recent_jobs`] for how limited lists are ordered.
+pub fn get_jobs(dest_name: Option<&str>, limit: Option<u32>) -> Result<Vec<JobInfo>> {
+    match limit {
+        Some(_) => get_recent_jobs(dest_name, JobFilter::All, limit),
+        None => get_jobs_with_filter(ptr::null_mut(), dest_name, false, WHICHJOBS_ALL),
+    }
+}
+
+/// List pending, held and processing jobs, or with `Some(limit)` only the most recent `limit`
+pub fn get_active_jobs(dest_name: Option<&str>, limit: Option<u32>) -> Result<Vec<JobInfo>> {
+    match limit {
+        Some(_) => get_recent_jobs(dest_name, JobFilter::Active, limit),
+        None => get_jobs_with_filter(
+            ptr::null_mut(),
+            dest_name,
+            false,
+            crate::constants::WHICHJOBS_ACTIVE,
+        ),
+    }
+}
+
+/// List finished jobs, or with `Some(limit)` only the most recent `limit`
+pub fn get_completed_jobs(dest_name: Option<&str>, limit: Option<u32>) -> Result<Vec<JobInfo>> {
+    match limit {
+        Some(_) => get_recent_jobs(dest_name, JobFilter::Completed, limit),
+        None => get_jobs_with_filter(
+            ptr::null_mut(),
+            dest_name,
+            false,
+            crate::constants::WHICHJOBS_COMPLETED,
+        ),
+    }
 }
 
-pub fn get_active_jobs(dest_name: Option<&str>) -> Result<Vec<JobInfo>> {
-    get_jobs_with_filter(dest_name, crate::constants::WHICHJOBS_ACTIVE)
+/// List only the jobs owned by the current CUPS user
+///
+/// The server filters the jobs by `cupsUser()`, so on a shared print server
+/// other users' jobs are never fetched.
+pub fn get_my_jobs(dest_name: Option<&str>) -> Result<Vec<JobInfo>> {
+    get_jobs_with_filter(ptr::null_mut(), dest_name, true, WHICHJOBS_ALL)
 }
 
-pub fn get_completed_jobs(dest_name: Option<&str>) -> Result<Vec<JobInfo>> {
-    get_jobs_with_filter(dest_name, crate::constants::WHICHJOBS_COMPLETED)
+/// List jobs selected by a typed [`JobFilter`]
+///
+/// Equivalent to `get_jobs`, `get_active_jobs` and `get_completed_jobs`, for
+/// callers that choose the filter at run time.
+pub fn get_jobs_filtered(dest_name: Option<&str>, filter: JobFilter) -> Result<Vec<JobInfo>> {
+    get_jobs_with_filter(ptr::null_mut(), dest_name, false, filter.to_cups())
 }
 
-fn get_jobs_with_filter(dest_name: Option<&str>, which_jobs: i32) -> Result<Vec<JobInfo>> {
+/// List the most recent `limit` jobs selected by `filter`, newest first
+///
+/// CUPS lists completed jobs newest first, so for [`JobFilter::Completed`]
+/// the IPP `limit` attribute is sent and the server stops after `limit`
+/// jobs rather than returning months of history. Active jobs, and all jobs,
+/// are listed oldest first, so those are fetched in full and the newest
+/// `limit` kept. `None` lists every matching job, newest first. Use
+/// [`JobQuery`](super::JobQuery) for more control over the request.
+pub fn get_recent_jobs(
+    dest_name: Option<&str>,
+    filter: JobFilter,
+    limit: Option<u32>,
+) -> Result<Vec<JobInfo>> {
+    let mut query = JobQuery::new(dest_name).which_jobs(filter);
+    if let (JobFilter::Completed, Some(limit)) = (filter, limit) {
+        query = query.limit(limit);
+    }
+
+    Ok(newest_first(query.run()?, limit))
+}
+
+/// Sort jobs by descending job id and keep at most `limit` of them
+fn newest_first(mut jobs: Vec<JobInfo>, limit: Option<u32>) -> Vec<JobInfo> {
+    jobs.sort_by(|a, b| b.id.cmp(&a.id));
+    if let Some(limit) = limit {
+        jobs.truncate(limit as usize);
+    }
+    jobs
+}
+
+/// List jobs over an existing connection instead of the default CUPS server
+///
+/// `filter` selects all, active or completed jobs.
+pub fn get_jobs_with_connection(
+    connection: &HttpConnection,
+    dest_name: Option<&str>,
+    filter: JobFilter,
+) -> Result<Vec<JobInfo>> {
+    get_jobs_with_filter(connection.checked_ptr()?, dest_name, false, filter.to_cups())
+}
+
+fn get_jobs_with_filter(
+    http: *mut bindings::_http_s,
+    dest_name: Option<&str>,
+    my_jobs: bool,
+    which_jobs: i32,
+) -> Result<Vec<JobInfo>> {
     let dest_name_c = match dest_name {
         Some(name) => Some(CString::new(name)?),
         None => None,
@@ -64,8 +248,9 @@ fn get_jobs_with_filter(dest_name: Option<&str>, which_jobs: i32) -> Result<Vec<
     };
 
     let mut jobs_ptr: *mut bindings::cups_job_s = ptr::null_mut();
-    let num_jobs =
-        unsafe { bindings::cupsGetJobs2(ptr::null_mut(), &mut jobs_ptr, dest_ptr, 0, which_jobs) };
+    let num_jobs = unsafe {
+        bindings::cupsGetJobs2(http, &mut jobs_ptr, dest_ptr, my_jobs as i32, which_jobs)
+    };
 
     if num_jobs < 0 {
         return Ok(Vec::new());
@@ -116,6 +301,8 @@ fn get_jobs_with_filter(dest_name: Option<&str>, which_jobs: i32) -> Result<Vec<
                 creation_time: job.creation_time as i64,
                 processing_time: job.processing_time as i64,
                 completed_time: job.completed_time as i64,
+                state_reasons: Vec::new(),
+                extra: HashMap::new(),
             });
         }
     }
@@ -129,14 +316,170 @@ fn get_jobs_with_filter(dest_name: Option<&str>, which_jobs: i32) -> Result<Vec<
     Ok(job_infos)
 }
 
+/// Get information about a single job
+///
+/// Queries the job directly with a Get-Job-Attributes request. If the direct
+/// query fails, falls back to scanning the full job list.
 pub fn get_job_info(job_id: i32) -> Result<JobInfo> {
-    let jobs = get_jobs(None)?;
+    match query_job_attributes(job_id) {
+        Ok(info) => Ok(info),
+        Err(_) => find_job_in_list(job_id),
+    }
+}
+
+impl Job {
+    /// Get a handle to an existing job from its id
+    ///
+    /// Looks the job up on the server and fills in its title, so the handle can
+    /// be used with `close`, `cancel`, `hold` and the other job operations.
+    /// `dest_name` may name an instance, e.g. `"Office/duplex"`; the job is
+    /// matched against the printer part. Fails if the job doesn't exist or is
+    /// queued on a different printer.
+    pub fn from_id(id: i32, dest_name: &str) -> Result<Job> {
+        let info = get_job_info(id)?;
+        let (printer, _) = split_full_name(dest_name);
+
+        if info.dest != printer {
+            return Err(Error::JobManagementFailed(format!(
+                "Job {} is queued on '{}', not '{}'",
+                id, info.dest, dest_name
+            )));
+        }
+
+        Ok(Job::new(id, dest_name.to_string(), info.title))
+    }
+
+    /// Get the `job-uri` identifying this job on the CUPS scheduler
+    pub fn uri(&self) -> String {
+        job_uri(self.id)
+    }
+}
+
+pub(super) const JOB_ATTRIBUTES: &[&str] = &[
+    "job-id",
+    "job-name",
+    "job-originating-user-name",
+    "job-printer-uri",
+    "job-state",
+    "job-state-reasons",
+    "job-k-octets",
+    "job-priority",
+    "time-at-creation",
+    "time-at-processing",
+    "time-at-completed",
+];
+
+fn query_job_attributes(job_id: i32) -> Result<JobInfo> {
+    let mut request = IppRequest::new(IppOperation::GetJobAttributes)?;
+    request.add_string(
+        IppTag::Operation,
+        IppValueTag::Uri,
+        "job-uri",
+        &job_uri(job_id),
+    )?;
+    request.add_string(
+        IppTag::Operation,
+        IppValueTag::Name,
+        "requesting-user-name",
+        &crate::config::get_user(),
+    )?;
+    request.add_strings(
+        IppTag::Operation,
+        IppValueTag::Keyword,
+        "requested-attributes",
+        JOB_ATTRIBUTES,
+    )?;
+
+    let response = request.send_with_http(ptr::null_mut(), "/")?;
+
+    if !response.is_successful() {
+        let (_, message) = get_cups_error_details();
+        return Err(Error::JobManagementFailed(format!(
+            "Failed to get attributes for job {}: {}",
+            job_id, message
+        )));
+    }
+
+    job_info_from_attributes(&response.attributes_in_group(IppTag::Job)).ok_or_else(|| {
+        Error::JobManagementFailed(format!("Job {} missing from server response", job_id))
+    })
+}
+
+/// Build a `JobInfo` from the attributes of one job group
+///
+/// Attributes outside `JOB_ATTRIBUTES` are collected into `JobInfo::extra`.
+pub(super) fn job_info_from_attributes(attributes: &[IppAttribute]) -> Option<JobInfo> {
+    let find = |name: &str| {
+        attributes
+            .iter()
+            .find(|attr| attr.name().as_deref() == Some(name))
+    };
+    let string_attr = |name: &str| {
+        find(name)
+            .and_then(|attr| attr.get_string(0))
+            .unwrap_or_default()
+    };
+    let integer_attr = |name: &str| find(name).map(|attr| attr.get_integer(0)).unwrap_or(0);
+
+    let id = find("job-id")?.get_integer(0);
+
+    // The queue name is the last segment of the decoded printer URI path,
+    // so escaped names such as `B%C3%BCro` compare equal to `Büro`
+    let dest = uri_resource(&string_attr("job-printer-uri"))
+        .and_then(|resource| resource.rsplit('/').next().map(str::to_string))
+        .unwrap_or_default();
+
+    let state_reasons = match find("job-state-reasons") {
+        Some(attr) => (0..attr.count())
+            .filter_map(|i| attr.get_string(i))
+            .flat_map(|reasons| {
+                reasons
+                    .split(',')
+                    .map(|s| s.trim().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let extra = attributes
+        .iter()
+        .filter_map(|attr| {
+            let name = attr.name()?;
+            if JOB_ATTRIBUTES.contains(&name.as_str()) {
+                return None;
+            }
+            let values: Vec<String> = (0..attr.count())
+                .filter_map(|i| attr.value_as_string(i))
+                .collect();
+            Some((name, values.join(",")))
+        })
+        .collect();
+
+    Some(JobInfo {
+        id,
+        title: string_attr("job-name"),
+        user: string_attr("job-originating-user-name"),
+        dest,
+        status: JobStatus::from_cups_state(integer_attr("job-state")),
+        size: integer_attr("job-k-octets"),
+        priority: integer_attr("job-priority"),
+        creation_time: integer_attr("time-at-creation") as i64,
+        processing_time: integer_attr("time-at-processing") as i64,
+        completed_time: integer_attr("time-at-completed") as i64,
+        state_reasons,
+        extra,
+    })
+}
+
+fn find_job_in_list(job_id: i32) -> Result<JobInfo> {
+    let jobs = get_jobs(None, None)?;
 
     jobs.into_iter()
         .find(|job| job.id == job_id)
         .ok_or_else(|| {
-            let active_jobs = get_active_jobs(None).unwrap_or_default();
-            let completed_jobs = get_completed_jobs(None).unwrap_or_default();
+            let active_jobs = get_active_jobs(None, None).unwrap_or_default();
+            let completed_jobs = get_completed_jobs(None, None).unwrap_or_default();
             Error::JobManagementFailed(format!(
                 "Job {} not found (active: {}, completed: {})",
                 job_id,
@@ -145,3 +488,37 @@ pub fn get_job_info(job_id: i32) -> Result<JobInfo> {
             ))
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(id: i32) -> JobInfo {
+        JobInfo {
+            id,
+            title: format!("Job {}", id),
+            user: "testuser".to_string(),
+            dest: "TestPrinter".to_string(),
+            status: JobStatus::Pending,
+            size: 0,
+            priority: 50,
+            creation_time: 0,
+            processing_time: 0,
+            completed_time: 0,
+            state_reasons: Vec::new(),
+            extra: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_newest_first() {
+        // Active and all-jobs lists arrive oldest first
+        let jobs = vec![job(3), job(5), job(9), job(12)];
+
+        let recent = newest_first(jobs.clone(), Some(2));
+        assert_eq!(recent.iter().map(|j| j.id).collect::<Vec<_>>(), vec![12, 9]);
+
+        let all = newest_first(jobs, None);
+        assert_eq!(all.iter().map(|j| j.id).collect::<Vec<_>>(), vec![12, 9, 5, 3]);
+    }
+}
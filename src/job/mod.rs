@@ -1,21 +1,33 @@
+mod document_uri;
 mod lifecycle;
 mod management;
 mod options;
+mod query;
 mod status;
 
-pub use management::{cancel_job, get_active_jobs, get_completed_jobs, get_job_info, get_jobs};
-pub use options::{ColorMode, DuplexMode, Orientation, PrintOptions, PrintQuality};
-pub use status::{JobInfo, JobStatus};
+pub use document_uri::create_job_with_document_uri;
+pub(crate) use management::printer_uri;
+pub use management::{
+    cancel_all_jobs, cancel_job, get_active_jobs, get_completed_jobs, get_job_info, get_jobs,
+    get_jobs_filtered, get_jobs_with_connection, get_my_jobs, get_recent_jobs, move_job,
+};
+pub use options::{
+    ColorMode, DuplexMode, Finishing, Orientation, PrintOptions, PrintQuality, Resolution,
+    ResolutionUnit, Scaling,
+};
+pub use query::JobQuery;
+pub use status::{JobFilter, JobInfo, JobStatus};
 
 use crate::bindings;
-use crate::destination::Destination;
+use crate::connection::HttpConnection;
+use crate::destination::{Destination, Destinations, split_full_name};
 use crate::error::{Error, Result};
 use crate::error_helpers::{
     check_document_size, cups_error_to_our_error, validate_document_format,
 };
 use std::ffi::CString;
 use std::fs::File;
-use std::io::Read;
+use std::io::{ErrorKind, Read};
 use std::path::Path;
 use std::ptr;
 
@@ -23,12 +35,19 @@ pub const FORMAT_PDF: &str = "application/pdf";
 pub const FORMAT_POSTSCRIPT: &str = "application/postscript";
 pub const FORMAT_TEXT: &str = "text/plain";
 pub const FORMAT_JPEG: &str = "image/jpeg";
+pub const FORMAT_PNG: &str = "image/png";
+pub const FORMAT_TIFF: &str = "image/tiff";
+pub const FORMAT_GIF: &str = "image/gif";
+pub const FORMAT_RAW: &str = "application/vnd.cups-raw";
+
+const DOCUMENT_CHUNK_SIZE: usize = 8192;
 
 #[derive(Debug, Clone)]
 pub struct Job {
     pub id: i32,
     pub dest_name: String,
     pub title: String,
+    max_document_size: Option<usize>,
 }
 
 impl Job {
@@ -37,9 +56,28 @@ impl Job {
             id,
             dest_name,
             title,
+            max_document_size: None,
         }
     }
 
+    /// Override the document size limit for files and data submitted to this job
+    ///
+    /// Use this for very large documents, e.g. plotter files, when the printer
+    /// doesn't advertise its own limit.
+    pub fn with_max_document_size(mut self, bytes: usize) -> Self {
+        self.max_document_size = Some(bytes);
+        self
+    }
+
+    /// Get the size limit in bytes set with [`Job::with_max_document_size`]
+    ///
+    /// `None` means documents are checked against the printer's advertised
+    /// `job-k-octets-supported` limit, or 100 MB if the printer doesn't
+    /// report one.
+    pub fn max_document_size(&self) -> Option<usize> {
+        self.max_document_size
+    }
+
     pub fn submit_file<P: AsRef<Path>>(&self, file_path: P, format: &str) -> Result<()> {
         self.submit_file_with_options(file_path, format, &[], true)
     }
@@ -60,29 +98,24 @@ impl Job {
             )));
         }
 
-        validate_document_format(format, &self.dest_name)?;
-
         let metadata = path.metadata().map_err(|e| {
             Error::DocumentSubmissionFailed(format!("Cannot access file metadata: {}", e))
         })?;
 
-        check_document_size(metadata.len() as usize, None)?;
-
-        let mut file = File::open(path)
+        let file = File::open(path)
             .map_err(|e| Error::DocumentSubmissionFailed(format!("Failed to open file: {}", e)))?;
 
-        let mut content = Vec::new();
-        file.read_to_end(&mut content)
-            .map_err(|e| Error::DocumentSubmissionFailed(format!("Failed to read file: {}", e)))?;
-
-        self.submit_data_with_options(
-            &content,
+        self.submit_document(
+            ptr::null_mut(),
+            file,
+            Some(metadata.len() as usize),
             format,
             path.file_name()
                 .and_then(|n| n.to_str())
                 .unwrap_or("document"),
             options,
             last_document,
+            &mut |_| {},
         )
     }
 
@@ -98,10 +131,138 @@ impl Job {
         options: &[(String, String)],
         last_document: bool,
     ) -> Result<()> {
-        validate_document_format(format, &self.dest_name)?;
-        check_document_size(data.len(), None)?;
+        self.submit_document(
+            ptr::null_mut(),
+            data,
+            Some(data.len()),
+            format,
+            doc_name,
+            options,
+            last_document,
+            &mut |_| {},
+        )
+    }
+
+    /// Get the document formats the job's destination accepts
+    ///
+    /// Reads the printer's `document-format-supported` attribute. Returns an
+    /// empty list if the printer doesn't report it.
+    pub fn supported_formats(&self) -> Result<Vec<String>> {
+        self.destination_on(ptr::null_mut())?
+            .supported_values("document-format")
+    }
+
+    /// Look up the job's destination on the server `http` is connected to
+    ///
+    /// A null `http` means the default server. The lookup goes over the same
+    /// connection as the job operation, so a job on a remote server is never
+    /// matched against the local queue list.
+    pub(crate) fn destination_on(&self, http: *mut bindings::_http_s) -> Result<Destination> {
+        let (name, instance) = split_full_name(&self.dest_name);
+        Destinations::get_named_on(http, name, instance)
+    }
+
+    /// Submit printer-ready data that CUPS sends to the printer untouched
+    ///
+    /// Use this for label and receipt printers that take their own command
+    /// language, such as ZPL or ESC/POS. No filters are run on the data.
+    pub fn submit_raw(&self, data: &[u8], doc_name: &str) -> Result<()> {
+        self.submit_data(data, FORMAT_RAW, doc_name)
+    }
+
+    /// Stream a document from any reader without buffering it in memory
+    ///
+    /// The document is read in fixed-size chunks and each chunk is written to
+    /// the printer as soon as it is read. Because the total size of an arbitrary
+    /// reader is unknown up front, no document size limit is applied.
+    pub fn submit_reader<R: Read>(&self, reader: R, format: &str, doc_name: &str) -> Result<()> {
+        self.submit_reader_with_options(reader, format, doc_name, &[], true)
+    }
+
+    pub fn submit_reader_with_options<R: Read>(
+        &self,
+        reader: R,
+        format: &str,
+        doc_name: &str,
+        options: &[(String, String)],
+        last_document: bool,
+    ) -> Result<()> {
+        self.submit_document(
+            ptr::null_mut(),
+            reader,
+            None,
+            format,
+            doc_name,
+            options,
+            last_document,
+            &mut |_| {},
+        )
+    }
+
+    /// Stream a document over an existing connection
+    ///
+    /// Use this for jobs created with [`create_job_with_connection`] so the
+    /// document goes to the same server as the job.
+    pub fn submit_reader_with_connection<R: Read>(
+        &self,
+        connection: &HttpConnection,
+        reader: R,
+        format: &str,
+        doc_name: &str,
+        options: &[(String, String)],
+        last_document: bool,
+    ) -> Result<()> {
+        self.submit_document(
+            connection.checked_ptr()?,
+            reader,
+            None,
+            format,
+            doc_name,
+            options,
+            last_document,
+            &mut |_| {},
+        )
+    }
+
+    /// Submit a document while reporting upload progress
+    ///
+    /// `progress` is called after every chunk written to the printer with
+    /// `(bytes_written, total_bytes)`. On success the last call reports
+    /// `bytes_written == total_bytes`, so empty data reports a single `(0, 0)`;
+    /// it is never called after a write error.
+    pub fn submit_data_with_progress(
+        &self,
+        data: &[u8],
+        format: &str,
+        doc_name: &str,
+        mut progress: impl FnMut(usize, usize),
+    ) -> Result<()> {
+        let total = data.len();
+        self.submit_document(
+            ptr::null_mut(),
+            data,
+            Some(total),
+            format,
+            doc_name,
+            &[],
+            true,
+            &mut |written| progress(written, total),
+        )
+    }
 
-        let dest = crate::get_destination(&self.dest_name)?;
+    #[allow(clippy::too_many_arguments)]
+    fn submit_document<R: Read>(
+        &self,
+        http: *mut bindings::_http_s,
+        reader: R,
+        size: Option<usize>,
+        format: &str,
+        doc_name: &str,
+        options: &[(String, String)],
+        last_document: bool,
+        progress: &mut dyn FnMut(usize),
+    ) -> Result<()> {
+        let dest = self.destination_on(http)?;
 
         if !dest.is_accepting_jobs() {
             return Err(Error::PrinterNotAccepting(
@@ -110,13 +271,26 @@ impl Job {
             ));
         }
 
-        let dest_info = dest.get_detailed_info(ptr::null_mut())?;
-        let dest_ptr = dest.as_ptr();
+        let raw_dest = dest.as_ptr();
+        let dest_info = dest.get_detailed_info_for(http, &raw_dest)?;
+        let dest_ptr = raw_dest.as_ptr();
 
         if dest_ptr.is_null() {
             return Err(Error::NullPointer);
         }
 
+        let supported_formats = dest_info
+            .get_supported_values(http, dest_ptr, "document-format")
+            .unwrap_or_default();
+        validate_document_format(format, &self.dest_name, &supported_formats)?;
+
+        if let Some(size) = size {
+            let limit = self
+                .max_document_size
+                .or_else(|| dest_info.max_document_size(http, dest_ptr));
+            check_document_size(size, limit)?;
+        }
+
         let doc_name_c = CString::new(doc_name)?;
         let format_c = CString::new(format)?;
 
@@ -139,7 +313,7 @@ impl Job {
 
         let status = unsafe {
             bindings::cupsStartDestDocument(
-                ptr::null_mut(),
+                http,
                 dest_ptr,
                 dest_info.as_ptr(),
                 self.id,
@@ -151,162 +325,201 @@ impl Job {
             )
         };
 
-        if status != bindings::http_status_e_HTTP_STATUS_CONTINUE as bindings::http_status_t {
-            unsafe {
-                if !cups_options_ptr.is_null() {
-                    bindings::cupsFreeOptions(num_options, cups_options_ptr);
-                }
+        let started =
+            status == bindings::http_status_e_HTTP_STATUS_CONTINUE as bindings::http_status_t;
 
-                let dest_box = Box::from_raw(dest_ptr);
-                if !dest_box.name.is_null() {
-                    let _ = CString::from_raw(dest_box.name);
-                }
-                if !dest_box.instance.is_null() {
-                    let _ = CString::from_raw(dest_box.instance);
-                }
-                if !dest_box.options.is_null() {
-                    bindings::cupsFreeOptions(dest_box.num_options, dest_box.options);
-                }
-            }
-
-            return Err(cups_error_to_our_error(
+        let result = if !started {
+            Err(cups_error_to_our_error(
                 "document start",
                 Some(&self.dest_name),
-            ));
-        }
-
-        let mut bytes_written = 0;
-        let mut remaining = data.len();
-
-        while remaining > 0 {
-            let chunk_size = remaining.min(8192);
-            let chunk = &data[bytes_written..bytes_written + chunk_size];
-
-            let result = unsafe {
-                bindings::cupsWriteRequestData(
-                    ptr::null_mut(),
-                    chunk.as_ptr() as *const ::std::os::raw::c_char,
-                    chunk_size,
-                )
-            };
-
-            if result != bindings::http_status_e_HTTP_STATUS_CONTINUE as bindings::http_status_t {
-                unsafe {
-                    if !cups_options_ptr.is_null() {
-                        bindings::cupsFreeOptions(num_options, cups_options_ptr);
-                    }
-
-                    let dest_box = Box::from_raw(dest_ptr);
-                    if !dest_box.name.is_null() {
-                        let _ = CString::from_raw(dest_box.name);
-                    }
-                    if !dest_box.instance.is_null() {
-                        let _ = CString::from_raw(dest_box.instance);
-                    }
-                    if !dest_box.options.is_null() {
-                        bindings::cupsFreeOptions(dest_box.num_options, dest_box.options);
-                    }
+            ))
+        } else {
+            write_document_data(http, reader, progress).and_then(|()| {
+                let finish_status = unsafe {
+                    bindings::cupsFinishDestDocument(http, dest_ptr, dest_info.as_ptr())
+                };
+
+                if finish_status == bindings::ipp_status_e_IPP_STATUS_OK as bindings::ipp_status_t {
+                    Ok(())
+                } else {
+                    Err(cups_error_to_our_error(
+                        "document finish",
+                        Some(&self.dest_name),
+                    ))
                 }
-
-                return Err(Error::DocumentSubmissionFailed(format!(
-                    "Failed to write data at byte {} (network error or timeout)",
-                    bytes_written
-                )));
-            }
-
-            bytes_written += chunk_size;
-            remaining -= chunk_size;
-        }
-
-        let finish_status = unsafe {
-            bindings::cupsFinishDestDocument(ptr::null_mut(), dest_ptr, dest_info.as_ptr())
+            })
         };
 
         unsafe {
             if !cups_options_ptr.is_null() {
                 bindings::cupsFreeOptions(num_options, cups_options_ptr);
             }
+        }
 
-            let dest_box = Box::from_raw(dest_ptr);
-            if !dest_box.name.is_null() {
-                let _ = CString::from_raw(dest_box.name);
-            }
-            if !dest_box.instance.is_null() {
-                let _ = CString::from_raw(dest_box.instance);
-            }
-            if !dest_box.options.is_null() {
-                bindings::cupsFreeOptions(dest_box.num_options, dest_box.options);
+        result
+    }
+}
+
+/// Copy a document from `reader` into the request started by `cupsStartDestDocument`
+///
+/// A read of zero bytes is end-of-file per the `Read` contract; interrupted reads
+/// are retried. Errors report the byte offset at which the transfer stopped.
+/// `progress` receives the running byte count after each successful write, or
+/// a single `0` if the document is empty.
+fn write_document_data<R: Read>(
+    http: *mut bindings::_http_s,
+    mut reader: R,
+    progress: &mut dyn FnMut(usize),
+) -> Result<()> {
+    let mut buffer = [0u8; DOCUMENT_CHUNK_SIZE];
+    let mut bytes_written = 0;
+
+    loop {
+        let chunk_size = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => {
+                return Err(Error::DocumentSubmissionFailed(format!(
+                    "Failed to read document at byte {}: {}",
+                    bytes_written, e
+                )));
             }
-        }
+        };
 
-        if finish_status == bindings::ipp_status_e_IPP_STATUS_OK as bindings::ipp_status_t {
-            Ok(())
-        } else {
-            Err(cups_error_to_our_error(
-                "document finish",
-                Some(&self.dest_name),
-            ))
+        let result = unsafe {
+            bindings::cupsWriteRequestData(
+                http,
+                buffer.as_ptr() as *const ::std::os::raw::c_char,
+                chunk_size,
+            )
+        };
+
+        if result != bindings::http_status_e_HTTP_STATUS_CONTINUE as bindings::http_status_t {
+            return Err(Error::DocumentSubmissionFailed(format!(
+                "Failed to write data at byte {} (network error or timeout)",
+                bytes_written
+            )));
         }
+
+        bytes_written += chunk_size;
+        progress(bytes_written);
     }
-}
 
-pub fn create_job(dest: &Destination, title: &str) -> Result<Job> {
-    if !dest.is_accepting_jobs() {
-        return Err(Error::PrinterNotAccepting(
-            dest.name.clone(),
-            "Printer is not accepting new jobs".to_string(),
-        ));
+    if bytes_written == 0 {
+        progress(0);
     }
 
-    let title_c = CString::new(title)?;
-    let dest_info = dest.get_detailed_info(ptr::null_mut())?;
-    let dest_ptr = dest.as_ptr();
+    Ok(())
+}
 
-    if dest_ptr.is_null() {
-        return Err(Error::NullPointer);
-    }
+pub fn create_job(dest: &Destination, title: &str) -> Result<Job> {
+    create_job_on(ptr::null_mut(), dest, title, &PrintOptions::new())
+}
 
-    let mut job_id: i32 = 0;
+pub fn create_job_with_options(
+    dest: &Destination,
+    title: &str,
+    options: &PrintOptions,
+) -> Result<Job> {
+    create_job_on(ptr::null_mut(), dest, title, options)
+}
 
-    let status = unsafe {
-        bindings::cupsCreateDestJob(
-            ptr::null_mut(),
-            dest_ptr,
-            dest_info.as_ptr(),
-            &mut job_id,
-            title_c.as_ptr(),
-            0,
-            ptr::null_mut(),
-        )
-    };
+/// Create a job over an existing connection instead of the default CUPS server
+///
+/// Submit documents with [`Job::submit_reader_with_connection`] and finish
+/// with [`Job::close_with_connection`] on the same connection.
+pub fn create_job_with_connection(
+    connection: &HttpConnection,
+    dest: &Destination,
+    title: &str,
+    options: &PrintOptions,
+) -> Result<Job> {
+    create_job_on(connection.checked_ptr()?, dest, title, options)
+}
 
-    unsafe {
-        let dest_box = Box::from_raw(dest_ptr);
+/// Print a file in one call: create the job, submit the file and close the job
+///
+/// The job is titled after the file name and the document format is guessed
+/// from the extension, falling back to `application/octet-stream` so CUPS
+/// auto-types the content. If submitting or closing fails, the job is
+/// cancelled so no half-finished job is left in the queue.
+pub fn print_file<P: AsRef<Path>>(
+    dest: &Destination,
+    path: P,
+    options: &PrintOptions,
+) -> Result<Job> {
+    let path = path.as_ref();
+    let title = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("document");
+
+    let job = create_job_with_options(dest, title, options)?;
+    finish_one_shot(job, |job| {
+        job.submit_file_with_options(path, format_for_path(path), &[], false)
+    })
+}
 
-        if !dest_box.name.is_null() {
-            let _ = CString::from_raw(dest_box.name);
-        }
-        if !dest_box.instance.is_null() {
-            let _ = CString::from_raw(dest_box.instance);
-        }
+/// Print in-memory data in one call: create the job, submit the data and close the job
+///
+/// The job is titled `doc_name`. Like [`print_file`], the job is cancelled if
+/// submitting or closing fails.
+pub fn print_data(
+    dest: &Destination,
+    data: &[u8],
+    format: &str,
+    doc_name: &str,
+    options: &PrintOptions,
+) -> Result<Job> {
+    let job = create_job_with_options(dest, doc_name, options)?;
+    finish_one_shot(job, |job| {
+        job.submit_data_with_options(data, format, doc_name, &[], false)
+    })
+}
 
-        if !dest_box.options.is_null() {
-            bindings::cupsFreeOptions(dest_box.num_options, dest_box.options);
+fn finish_one_shot<F>(job: Job, submit: F) -> Result<Job>
+where
+    F: FnOnce(&Job) -> Result<()>,
+{
+    match submit(&job).and_then(|_| job.close()) {
+        Ok(()) => Ok(job),
+        Err(e) => {
+            if let Err(cancel_err) = job.cancel() {
+                eprintln!("Warning: Failed to cancel job {}: {}", job.id, cancel_err);
+            }
+            Err(e)
         }
     }
+}
 
-    if status == bindings::ipp_status_e_IPP_STATUS_OK as bindings::ipp_status_t {
-        Ok(Job::new(job_id, dest.name.clone(), title.to_string()))
-    } else {
-        Err(cups_error_to_our_error("job creation", Some(&dest.name)))
+/// Guess a document format from a file extension
+fn format_for_path(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase());
+
+    match extension.as_deref() {
+        Some("pdf") => FORMAT_PDF,
+        Some("ps") => FORMAT_POSTSCRIPT,
+        Some("txt") | Some("text") => FORMAT_TEXT,
+        Some("jpg") | Some("jpeg") => FORMAT_JPEG,
+        Some("png") => FORMAT_PNG,
+        Some("tif") | Some("tiff") => FORMAT_TIFF,
+        Some("gif") => FORMAT_GIF,
+        _ => "application/octet-stream",
     }
 }
 
-pub fn create_job_with_options(
+fn create_job_on(
+    http: *mut bindings::_http_s,
     dest: &Destination,
     title: &str,
     options: &PrintOptions,
 ) -> Result<Job> {
+    options.check()?;
+
     if !dest.is_accepting_jobs() {
         return Err(Error::PrinterNotAccepting(
             dest.name.clone(),
@@ -315,8 +528,9 @@ pub fn create_job_with_options(
     }
 
     let title_c = CString::new(title)?;
-    let dest_info = dest.get_detailed_info(ptr::null_mut())?;
-    let dest_ptr = dest.as_ptr();
+    let raw_dest = dest.as_ptr();
+    let dest_info = dest.get_detailed_info_for(http, &raw_dest)?;
+    let dest_ptr = raw_dest.as_ptr();
 
     if dest_ptr.is_null() {
         return Err(Error::NullPointer);
@@ -344,7 +558,7 @@ pub fn create_job_with_options(
 
     let status = unsafe {
         bindings::cupsCreateDestJob(
-            ptr::null_mut(),
+            http,
             dest_ptr,
             dest_info.as_ptr(),
             &mut job_id,
@@ -358,27 +572,140 @@ pub fn create_job_with_options(
         if !cups_options_ptr.is_null() {
             bindings::cupsFreeOptions(num_options, cups_options_ptr);
         }
-
-        let dest_box = Box::from_raw(dest_ptr);
-
-        if !dest_box.name.is_null() {
-            let _ = CString::from_raw(dest_box.name);
-        }
-        if !dest_box.instance.is_null() {
-            let _ = CString::from_raw(dest_box.instance);
-        }
-
-        if !dest_box.options.is_null() {
-            bindings::cupsFreeOptions(dest_box.num_options, dest_box.options);
-        }
     }
 
     if status == bindings::ipp_status_e_IPP_STATUS_OK as bindings::ipp_status_t {
         Ok(Job::new(job_id, dest.name.clone(), title.to_string()))
     } else {
-        Err(cups_error_to_our_error(
-            "job creation with options",
-            Some(&dest.name),
-        ))
+        Err(cups_error_to_our_error("job creation", Some(&dest.name)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipp::IppAttribute;
+
+    #[test]
+    fn test_max_document_size() {
+        let job = Job::new(1, "Plotter".to_string(), "Drawing".to_string());
+        assert_eq!(job.max_document_size(), None);
+
+        let job = job.with_max_document_size(200 * 1024 * 1024);
+        assert_eq!(job.max_document_size(), Some(200 * 1024 * 1024));
+        assert!(check_document_size(150 * 1024 * 1024, job.max_document_size()).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_release_pin_fails_submission() {
+        let dest = Destination::new("Vault");
+        let options = PrintOptions::new().copies(1).release_pin("");
+
+        let result = create_job_with_options(&dest, "Payroll", &options);
+        assert!(matches!(result, Err(Error::ConfigurationError(_))));
+
+        let path = Path::new("payroll.pdf");
+        assert!(matches!(
+            print_file(&dest, path, &options),
+            Err(Error::ConfigurationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_empty_document_reports_progress() {
+        let mut calls = Vec::new();
+        write_document_data(ptr::null_mut(), &[][..], &mut |written| calls.push(written))
+            .unwrap();
+        assert_eq!(calls, vec![0]);
+    }
+
+    #[test]
+    fn test_connection_lookup_uses_connection() {
+        use crate::config::EncryptionMode;
+
+        // An unconnected http_t to a port nothing listens on; CUPS only
+        // records a connection error on it if a request is sent over it
+        let unconnected = || {
+            let host = CString::new("127.0.0.1").unwrap();
+            let http = unsafe {
+                bindings::httpConnect2(
+                    host.as_ptr(),
+                    1,
+                    ptr::null_mut(),
+                    libc::AF_UNSPEC,
+                    EncryptionMode::Never.into(),
+                    1,
+                    0,
+                    ptr::null_mut(),
+                )
+            };
+            unsafe { HttpConnection::from_raw(http, String::new()) }.unwrap()
+        };
+        let used = |connection: &HttpConnection| unsafe { bindings::httpError(connection.as_ptr()) } != 0;
+
+        let job = Job::new(1, "RemoteQueue/draft".to_string(), "Report".to_string());
+
+        let connection = unconnected();
+        assert!(!used(&connection));
+        assert!(job.cancel_with_connection(&connection).is_err());
+        assert!(used(&connection));
+
+        let connection = unconnected();
+        assert!(job.close_with_connection(&connection).is_err());
+        assert!(used(&connection));
+
+        let connection = unconnected();
+        assert!(job
+            .submit_reader_with_connection(&connection, &b"data"[..], FORMAT_TEXT, "doc", &[], true)
+            .is_err());
+        assert!(used(&connection));
+    }
+
+    #[test]
+    fn test_set_attributes_on_instance_job() {
+        let job = Job::new(42, "Office/duplex".to_string(), "Report".to_string());
+        let request = job
+            .set_attributes_request(&[("job-priority".to_string(), "90".to_string())])
+            .unwrap();
+
+        let find = |name: &str| {
+            let name_c = CString::new(name).unwrap();
+            IppAttribute::from_raw(unsafe {
+                bindings::ippFindAttribute(
+                    request.as_ptr(),
+                    name_c.as_ptr(),
+                    bindings::ipp_tag_e_IPP_TAG_ZERO,
+                )
+            })
+        };
+
+        assert_eq!(
+            find("job-uri").and_then(|attr| attr.get_string(0)),
+            Some("ipp://localhost/jobs/42".to_string())
+        );
+        assert!(find("printer-uri").is_none());
+        assert_eq!(find("job-priority").map(|attr| attr.get_integer(0)), Some(90));
+    }
+
+    #[test]
+    fn test_job_uri() {
+        let job = Job::new(42, "Office".to_string(), "Report".to_string());
+        assert_eq!(job.uri(), "ipp://localhost/jobs/42");
+    }
+
+    #[test]
+    fn test_format_for_path() {
+        assert_eq!(format_for_path(Path::new("report.pdf")), FORMAT_PDF);
+        assert_eq!(format_for_path(Path::new("REPORT.PDF")), FORMAT_PDF);
+        assert_eq!(format_for_path(Path::new("page.ps")), FORMAT_POSTSCRIPT);
+        assert_eq!(format_for_path(Path::new("notes.txt")), FORMAT_TEXT);
+        assert_eq!(format_for_path(Path::new("photo.jpeg")), FORMAT_JPEG);
+        assert_eq!(format_for_path(Path::new("scan.TIF")), FORMAT_TIFF);
+        assert_eq!(format_for_path(Path::new("banner.gif")), FORMAT_GIF);
+        assert_eq!(format_for_path(Path::new("scan.png")), FORMAT_PNG);
+        assert_eq!(
+            format_for_path(Path::new("archive")),
+            "application/octet-stream"
+        );
     }
 }
@@ -1,18 +1,53 @@
+use crate::bindings;
 use crate::constants::*;
+use crate::destination::{Destination, MediaSize};
+use crate::error::{Error, Result};
 use std::collections::HashMap;
+use std::ptr;
+
+const NUMBER_UP_VALUES: [u32; 6] = [1, 2, 4, 6, 9, 16];
+const NUMBER_UP_LAYOUTS: [&str; 8] = [
+    "btlr", "btrl", "lrbt", "lrtb", "rlbt", "rltb", "tblr", "tbrl",
+];
+const PAGE_BORDERS: [&str; 5] = ["none", "single", "single-thick", "double", "double-thick"];
+const JOB_PASSWORD_MAX_LEN: usize = 255;
 
 #[derive(Debug, Clone)]
 pub struct PrintOptions {
     options: HashMap<String, String>,
+    /// Builder calls whose values were rejected, reported by `check`
+    invalid: Vec<String>,
 }
 
 impl PrintOptions {
     pub fn new() -> Self {
         Self {
             options: HashMap::new(),
+            invalid: Vec::new(),
+        }
+    }
+
+    /// Create options from a map of option names to values, such as saved
+    /// user preferences
+    pub fn from_map(options: HashMap<String, String>) -> Self {
+        Self {
+            options,
+            invalid: Vec::new(),
         }
     }
 
+    /// Combine with another set of options; values from `other` win on conflicts
+    pub fn merge(mut self, other: PrintOptions) -> Self {
+        self.options.extend(other.options);
+        self.invalid.extend(other.invalid);
+        self
+    }
+
+    /// Get the value of an option by name
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.options.get(key).map(String::as_str)
+    }
+
     pub fn copies(mut self, count: u32) -> Self {
         self.options.insert(COPIES.to_string(), count.to_string());
         self
@@ -23,6 +58,41 @@ impl PrintOptions {
         self
     }
 
+    /// Select a media size returned by `Destination::all_media` and friends
+    ///
+    /// A size without a name is rejected, see [`check`](Self::check).
+    pub fn media_size(self, size: &MediaSize) -> Self {
+        if size.as_media_option().is_empty() {
+            return self.reject("media size without a name".to_string());
+        }
+        self.media(size.as_media_option())
+    }
+
+    /// Select the input tray or paper source, e.g. `MEDIA_SOURCE_MANUAL`
+    pub fn media_source(mut self, source: &str) -> Self {
+        self.options
+            .insert(MEDIA_SOURCE.to_string(), source.to_string());
+        self
+    }
+
+    /// Select a numbered input tray, formatted as the `tray-N` keyword
+    ///
+    /// Trays are numbered from 1; tray 0 is rejected.
+    pub fn media_source_tray(self, tray: u32) -> Self {
+        if tray == 0 {
+            return self.reject("media source tray 0".to_string());
+        }
+
+        self.media_source(&format!("tray-{}", tray))
+    }
+
+    /// Select the kind of media to print on, e.g. `MEDIA_TYPE_LABELS`
+    pub fn media_type(mut self, media_type: &str) -> Self {
+        self.options
+            .insert(MEDIA_TYPE.to_string(), media_type.to_string());
+        self
+    }
+
     pub fn color_mode(mut self, mode: ColorMode) -> Self {
         self.options
             .insert(PRINT_COLOR_MODE.to_string(), mode.to_string());
@@ -46,6 +116,218 @@ impl PrintOptions {
         self
     }
 
+    /// Request a specific print resolution
+    pub fn resolution(mut self, resolution: Resolution) -> Self {
+        self.options
+            .insert(PRINTER_RESOLUTION.to_string(), resolution.to_string());
+        self
+    }
+
+    /// Control how document pages are scaled to the media
+    pub fn scaling(mut self, scaling: Scaling) -> Self {
+        self.options
+            .insert(PRINT_SCALING.to_string(), scaling.to_string());
+        self
+    }
+
+    /// Apply finishing processes such as stapling or punching
+    ///
+    /// An empty slice leaves the options unchanged.
+    pub fn finishings(mut self, finishings: &[Finishing]) -> Self {
+        if finishings.is_empty() {
+            return self;
+        }
+
+        let value = finishings
+            .iter()
+            .map(|f| f.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        self.options.insert(FINISHINGS.to_string(), value);
+        self
+    }
+
+    /// Print multiple pages on each sheet
+    ///
+    /// Supported values are 1, 2, 4, 6, 9 and 16. Other values are rejected.
+    pub fn number_up(mut self, pages_per_sheet: u32) -> Self {
+        if !NUMBER_UP_VALUES.contains(&pages_per_sheet) {
+            return self.reject(format!("unsupported number-up value {}", pages_per_sheet));
+        }
+
+        self.options
+            .insert(NUMBER_UP.to_string(), pages_per_sheet.to_string());
+        self
+    }
+
+    /// Set the order in which pages are placed on a sheet with `number_up`
+    ///
+    /// The layout is a keyword such as `"lrtb"` (left to right, top to bottom),
+    /// `"rltb"`, `"tblr"` or `"btrl"`. Unknown layouts are rejected.
+    pub fn number_up_layout(mut self, layout: &str) -> Self {
+        if !NUMBER_UP_LAYOUTS.contains(&layout) {
+            return self.reject(format!("unsupported number-up-layout '{}'", layout));
+        }
+
+        self.options
+            .insert(NUMBER_UP_LAYOUT.to_string(), layout.to_string());
+        self
+    }
+
+    /// Draw a border around each page placed on a sheet with `number_up`
+    ///
+    /// The border is one of `"none"`, `"single"`, `"single-thick"`, `"double"`
+    /// or `"double-thick"`. Unknown values are rejected.
+    pub fn page_border(mut self, border: &str) -> Self {
+        if !PAGE_BORDERS.contains(&border) {
+            return self.reject(format!("unsupported page-border '{}'", border));
+        }
+
+        self.options
+            .insert(PAGE_BORDER.to_string(), border.to_string());
+        self
+    }
+
+    /// Set the number of characters per inch for plain text documents
+    ///
+    /// Non-positive values are rejected.
+    pub fn chars_per_inch(mut self, cpi: f32) -> Self {
+        if !(cpi.is_finite() && cpi > 0.0) {
+            return self.reject(format!("invalid cpi value {}", cpi));
+        }
+
+        self.options.insert(CPI.to_string(), cpi.to_string());
+        self
+    }
+
+    /// Set the number of lines per inch for plain text documents
+    ///
+    /// Non-positive values are rejected.
+    pub fn lines_per_inch(mut self, lpi: f32) -> Self {
+        if !(lpi.is_finite() && lpi > 0.0) {
+            return self.reject(format!("invalid lpi value {}", lpi));
+        }
+
+        self.options.insert(LPI.to_string(), lpi.to_string());
+        self
+    }
+
+    /// Set the number of text columns on each page for plain text documents
+    ///
+    /// Zero is rejected.
+    pub fn columns(mut self, columns: u32) -> Self {
+        if columns == 0 {
+            return self.reject("columns value 0".to_string());
+        }
+
+        self.options.insert(COLUMNS.to_string(), columns.to_string());
+        self
+    }
+
+    /// Collate copies of multi-page documents
+    ///
+    /// `true` prints complete sets (1,2,3,1,2,3); `false` groups copies of each
+    /// page together (1,1,2,2,3,3). Only matters when printing more than one copy.
+    pub fn collate(mut self, collate: bool) -> Self {
+        let value = if collate {
+            "separate-documents-collated-copies"
+        } else {
+            "separate-documents-uncollated-copies"
+        };
+        self.options
+            .insert(MULTIPLE_DOCUMENT_HANDLING.to_string(), value.to_string());
+        self
+    }
+
+    /// Deliver pages last-to-first, e.g. for face-up output trays
+    pub fn reverse_order(mut self, reverse: bool) -> Self {
+        let value = if reverse { "reverse-order" } else { "same-order" };
+        self.options
+            .insert(PAGE_DELIVERY.to_string(), value.to_string());
+        self
+    }
+
+    /// Print only the given page ranges
+    ///
+    /// Each range is an inclusive `(first, last)` pair; use `(n, n)` for a single
+    /// page. Ranges must start at page 1 or later, be in ascending order and must
+    /// not overlap. Malformed ranges are rejected rather than printing the
+    /// whole document; an empty slice leaves the options unchanged.
+    pub fn page_ranges(mut self, ranges: &[(u32, u32)]) -> Self {
+        let mut previous_last = 0;
+        for &(first, last) in ranges {
+            if first == 0 || first > last || first <= previous_last {
+                return self.reject(format!("invalid page ranges {:?}", ranges));
+            }
+            previous_last = last;
+        }
+
+        if ranges.is_empty() {
+            return self;
+        }
+
+        let value = ranges
+            .iter()
+            .map(|&(first, last)| {
+                if first == last {
+                    first.to_string()
+                } else {
+                    format!("{}-{}", first, last)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        self.options.insert(PAGE_RANGES.to_string(), value);
+        self
+    }
+
+    /// Set the job priority, from 1 (lowest) to 100 (highest)
+    ///
+    /// Values outside that range are clamped. The CUPS default is 50.
+    pub fn priority(mut self, priority: u32) -> Self {
+        self.options
+            .insert(JOB_PRIORITY.to_string(), priority.clamp(1, 100).to_string());
+        self
+    }
+
+    /// Hold the job until the given time or period
+    ///
+    /// Accepts a time of day in UTC (`"22:00"` or `"22:00:00"`) or one of the
+    /// standard keywords:
+    /// - `"indefinite"`: hold until released manually
+    /// - `"day-time"`: 6am to 6pm
+    /// - `"evening"`: 6pm to 6am
+    /// - `"night"`: 6pm to 6am
+    /// - `"second-shift"`: 4pm to 12am
+    /// - `"third-shift"`: 12am to 8am
+    /// - `"weekend"`: Saturday and Sunday
+    /// - `"no-hold"`: print immediately
+    pub fn hold_until(mut self, hold_until: &str) -> Self {
+        self.options
+            .insert(JOB_HOLD_UNTIL.to_string(), hold_until.to_string());
+        self
+    }
+
+    /// Hold the job at the printer until the given PIN is entered on its panel
+    ///
+    /// Sets `job-password` to the PIN and `job-password-encryption` to `none`,
+    /// so the printer receives the PIN as a plain IPP octet string. The
+    /// printer must advertise `job-password-supported`; check with
+    /// `printer.supports_option(JOB_PASSWORD)`. Empty PINs and PINs longer than
+    /// 255 bytes are rejected: [`check`](Self::check) fails and no job is
+    /// created with these options.
+    pub fn release_pin(mut self, pin: &str) -> Self {
+        if pin.is_empty() || pin.len() > JOB_PASSWORD_MAX_LEN {
+            return self.reject(format!("release PIN of invalid length {}", pin.len()));
+        }
+
+        self.options.insert(JOB_PASSWORD.to_string(), pin.to_string());
+        self.options
+            .insert(JOB_PASSWORD_ENCRYPTION.to_string(), "none".to_string());
+        self
+    }
+
     pub fn custom_option<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
         self.options.insert(key.into(), value.into());
         self
@@ -65,6 +347,87 @@ impl PrintOptions {
     pub fn is_empty(&self) -> bool {
         self.options.is_empty()
     }
+
+    /// Fail if any builder call was given an invalid value
+    ///
+    /// Builders such as `page_ranges`, `number_up` and `release_pin` never add
+    /// a rejected value, so printing anyway would silently drop the setting.
+    /// Job creation and `validate` call this first and refuse such options.
+    pub fn check(&self) -> Result<()> {
+        if self.invalid.is_empty() {
+            return Ok(());
+        }
+
+        Err(Error::ConfigurationError(format!(
+            "Invalid print options: {}",
+            self.invalid.join("; ")
+        )))
+    }
+
+    fn reject(mut self, reason: String) -> Self {
+        self.invalid.push(reason);
+        self
+    }
+
+    /// Check these options against a destination's capabilities
+    ///
+    /// Each option/value pair is checked with `DestinationInfo::is_value_supported`;
+    /// comma-separated values such as `finishings` or `page-ranges` are checked
+    /// one element at a time, and `copies` must fall within the destination's
+    /// `copies-supported` range. Supported options are then checked against each
+    /// other with `DestinationInfo::check_option_conflicts`.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<String>)` - Names of unsupported options, sorted (empty if all are supported)
+    /// * `Err(Error)` - An invalid builder value, destination info unavailable or
+    ///   an unresolvable option conflict
+    pub fn validate(&self, dest: &Destination) -> Result<Vec<String>> {
+        self.check()?;
+
+        let raw_dest = dest.as_ptr();
+        let info = dest.get_detailed_info_for(ptr::null_mut(), &raw_dest)?;
+        let dest_ptr = raw_dest.as_ptr();
+
+        let mut names: Vec<&String> = self.options.keys().collect();
+        names.sort();
+
+        let mut unsupported = Vec::new();
+        let mut accepted: Vec<(String, String)> = Vec::new();
+
+        for name in names {
+            let value = &self.options[name];
+            let supported = if name == COPIES {
+                copies_in_range(value, info.copies_range(ptr::null_mut(), dest_ptr))
+                    && info.is_value_supported(ptr::null_mut(), dest_ptr, name, value)
+            } else if name == JOB_PASSWORD {
+                // The PIN itself is never listed as a supported value
+                info.is_option_supported(ptr::null_mut(), dest_ptr, name)
+            } else {
+                value.split(',').all(|v| {
+                    info.is_value_supported(ptr::null_mut(), dest_ptr, name, v.trim())
+                })
+            };
+
+            if !supported {
+                unsupported.push(name.clone());
+                continue;
+            }
+
+            info.check_option_conflicts(dest, &accepted, name, value)?;
+            accepted.push((name.clone(), value.clone()));
+        }
+
+        Ok(unsupported)
+    }
+}
+
+/// Check a `copies` value against a `copies-supported` range, if there is one
+fn copies_in_range(value: &str, range: Option<(i32, i32)>) -> bool {
+    match (value.parse::<i32>(), range) {
+        (Ok(copies), Some((min, max))) => (min..=max).contains(&copies),
+        (Ok(_), None) => true,
+        (Err(_), _) => false,
+    }
 }
 
 impl Default for PrintOptions {
@@ -73,13 +436,25 @@ impl Default for PrintOptions {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ColorMode {
     Auto,
     Color,
     Monochrome,
 }
 
+impl ColorMode {
+    /// Parse a `print-color-mode` value, returning None for unknown values
+    pub fn from_cups_value(value: &str) -> Option<Self> {
+        match value {
+            PRINT_COLOR_MODE_AUTO => Some(ColorMode::Auto),
+            PRINT_COLOR_MODE_COLOR => Some(ColorMode::Color),
+            PRINT_COLOR_MODE_MONOCHROME => Some(ColorMode::Monochrome),
+            _ => None,
+        }
+    }
+}
+
 impl ToString for ColorMode {
     fn to_string(&self) -> String {
         match self {
@@ -90,13 +465,25 @@ impl ToString for ColorMode {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PrintQuality {
     Draft,
     Normal,
     High,
 }
 
+impl PrintQuality {
+    /// Parse a `print-quality` value such as `"5"`, returning None for unknown values
+    pub fn from_cups_value(value: &str) -> Option<Self> {
+        match value {
+            PRINT_QUALITY_DRAFT => Some(PrintQuality::Draft),
+            PRINT_QUALITY_NORMAL => Some(PrintQuality::Normal),
+            PRINT_QUALITY_HIGH => Some(PrintQuality::High),
+            _ => None,
+        }
+    }
+}
+
 impl ToString for PrintQuality {
     fn to_string(&self) -> String {
         match self {
@@ -107,13 +494,25 @@ impl ToString for PrintQuality {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DuplexMode {
     OneSided,
     TwoSidedPortrait,
     TwoSidedLandscape,
 }
 
+impl DuplexMode {
+    /// Parse a `sides` value, returning None for unknown values
+    pub fn from_cups_value(value: &str) -> Option<Self> {
+        match value {
+            SIDES_ONE_SIDED => Some(DuplexMode::OneSided),
+            SIDES_TWO_SIDED_PORTRAIT => Some(DuplexMode::TwoSidedPortrait),
+            SIDES_TWO_SIDED_LANDSCAPE => Some(DuplexMode::TwoSidedLandscape),
+            _ => None,
+        }
+    }
+}
+
 impl ToString for DuplexMode {
     fn to_string(&self) -> String {
         match self {
@@ -124,12 +523,23 @@ impl ToString for DuplexMode {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Orientation {
     Portrait,
     Landscape,
 }
 
+impl Orientation {
+    /// Parse an `orientation-requested` value, returning None for unknown values
+    pub fn from_cups_value(value: &str) -> Option<Self> {
+        match value {
+            ORIENTATION_PORTRAIT => Some(Orientation::Portrait),
+            ORIENTATION_LANDSCAPE => Some(Orientation::Landscape),
+            _ => None,
+        }
+    }
+}
+
 impl ToString for Orientation {
     fn to_string(&self) -> String {
         match self {
@@ -139,6 +549,145 @@ impl ToString for Orientation {
     }
 }
 
+/// Units for a [`Resolution`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolutionUnit {
+    /// Dots per inch
+    Dpi,
+    /// Dots per centimeter
+    Dpcm,
+}
+
+impl ResolutionUnit {
+    pub(crate) fn from_ipp(units: bindings::ipp_res_t) -> Self {
+        match units {
+            bindings::ipp_res_e_IPP_RES_PER_CM => ResolutionUnit::Dpcm,
+            _ => ResolutionUnit::Dpi,
+        }
+    }
+}
+
+impl std::fmt::Display for ResolutionUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResolutionUnit::Dpi => f.write_str("dpi"),
+            ResolutionUnit::Dpcm => f.write_str("dpcm"),
+        }
+    }
+}
+
+/// A print resolution, such as 600dpi or 1200x600dpi
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Resolution {
+    /// Horizontal (cross-feed) resolution
+    pub x: u32,
+    /// Vertical (feed) resolution
+    pub y: u32,
+    pub unit: ResolutionUnit,
+}
+
+impl std::fmt::Display for Resolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.x == self.y {
+            write!(f, "{}{}", self.x, self.unit)
+        } else {
+            write!(f, "{}x{}{}", self.x, self.y, self.unit)
+        }
+    }
+}
+
+/// Values for the IPP `print-scaling` attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scaling {
+    /// Fit or fill depending on the document and media (printer decides)
+    Auto,
+    /// Shrink to fit only if the page is larger than the printable area
+    AutoFit,
+    /// Scale to fill the media, cropping if needed (borderless photos)
+    Fill,
+    /// Scale to fit the printable area, preserving aspect ratio
+    Fit,
+    /// Print at the original size
+    None,
+}
+
+impl std::fmt::Display for Scaling {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Scaling::Auto => PRINT_SCALING_AUTO,
+            Scaling::AutoFit => PRINT_SCALING_AUTO_FIT,
+            Scaling::Fill => PRINT_SCALING_FILL,
+            Scaling::Fit => PRINT_SCALING_FIT,
+            Scaling::None => PRINT_SCALING_NONE,
+        };
+        f.write_str(value)
+    }
+}
+
+/// Finishing processes from the IPP `finishings` attribute (PWG 5100.1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Finishing {
+    None = 3,
+    Staple = 4,
+    Punch = 5,
+    Cover = 6,
+    Bind = 7,
+    SaddleStitch = 8,
+    EdgeStitch = 9,
+    Fold = 10,
+    Trim = 11,
+    BookletMaker = 13,
+    JogOffset = 14,
+    StapleTopLeft = 20,
+    StapleBottomLeft = 21,
+    StapleTopRight = 22,
+    StapleBottomRight = 23,
+    StapleDualLeft = 28,
+    StapleDualTop = 29,
+    StapleDualRight = 30,
+    StapleDualBottom = 31,
+}
+
+impl Finishing {
+    /// Convert an IPP `finishings` enum value, returning None for values not
+    /// covered by this type
+    pub fn from_ipp(value: i32) -> Option<Self> {
+        match value {
+            3 => Some(Finishing::None),
+            4 => Some(Finishing::Staple),
+            5 => Some(Finishing::Punch),
+            6 => Some(Finishing::Cover),
+            7 => Some(Finishing::Bind),
+            8 => Some(Finishing::SaddleStitch),
+            9 => Some(Finishing::EdgeStitch),
+            10 => Some(Finishing::Fold),
+            11 => Some(Finishing::Trim),
+            13 => Some(Finishing::BookletMaker),
+            14 => Some(Finishing::JogOffset),
+            20 => Some(Finishing::StapleTopLeft),
+            21 => Some(Finishing::StapleBottomLeft),
+            22 => Some(Finishing::StapleTopRight),
+            23 => Some(Finishing::StapleBottomRight),
+            28 => Some(Finishing::StapleDualLeft),
+            29 => Some(Finishing::StapleDualTop),
+            30 => Some(Finishing::StapleDualRight),
+            31 => Some(Finishing::StapleDualBottom),
+            _ => Option::None,
+        }
+    }
+
+    /// Get the IPP `finishings` enum value
+    pub fn to_ipp(self) -> i32 {
+        self as i32
+    }
+}
+
+impl std::fmt::Display for Finishing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_ipp())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -187,6 +736,256 @@ mod tests {
         assert_eq!(option_map.get("another-key"), Some(&"another-value"));
     }
 
+    #[test]
+    fn test_from_map_and_merge() {
+        let mut saved = HashMap::new();
+        saved.insert("copies".to_string(), "2".to_string());
+        saved.insert("media".to_string(), crate::MEDIA_A4.to_string());
+
+        let preferences = PrintOptions::from_map(saved);
+        assert_eq!(preferences.get("copies"), Some("2"));
+        assert_eq!(preferences.get("sides"), None);
+
+        let merged = preferences.merge(
+            PrintOptions::new()
+                .copies(5)
+                .duplex(DuplexMode::TwoSidedPortrait),
+        );
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged.get("copies"), Some("5"));
+        assert_eq!(merged.get("media"), Some(crate::MEDIA_A4));
+        assert_eq!(merged.get("sides"), Some("two-sided-long-edge"));
+    }
+
+    #[test]
+    fn test_resolution() {
+        let square = Resolution {
+            x: 600,
+            y: 600,
+            unit: ResolutionUnit::Dpi,
+        };
+        assert_eq!(square.to_string(), "600dpi");
+
+        let options = PrintOptions::new().resolution(Resolution {
+            x: 600,
+            y: 300,
+            unit: ResolutionUnit::Dpi,
+        });
+        assert_eq!(
+            options.as_cups_options(),
+            vec![("printer-resolution", "600x300dpi")]
+        );
+
+        let metric = Resolution {
+            x: 118,
+            y: 118,
+            unit: ResolutionUnit::Dpcm,
+        };
+        assert_eq!(metric.to_string(), "118dpcm");
+    }
+
+    #[test]
+    fn test_scaling() {
+        let options = PrintOptions::new().scaling(Scaling::Fill);
+        assert_eq!(options.as_cups_options(), vec![("print-scaling", "fill")]);
+    }
+
+    #[test]
+    fn test_media_size() {
+        let a5 = MediaSize::custom(148.0, 210.0);
+        let options = PrintOptions::new().media_size(&a5);
+        assert_eq!(options.as_cups_options(), vec![("media", "custom_148x210mm_148x210mm")]);
+
+        let unnamed = MediaSize {
+            name: String::new(),
+            ..a5
+        };
+        let rejected = PrintOptions::new().media_size(&unnamed);
+        assert!(rejected.is_empty());
+        assert!(rejected.check().is_err());
+    }
+
+    #[test]
+    fn test_media_source_and_type() {
+        let options = PrintOptions::new()
+            .media_source(crate::MEDIA_SOURCE_MANUAL)
+            .media_type(crate::MEDIA_TYPE_LABELS);
+        let option_map: std::collections::HashMap<&str, &str> =
+            options.as_cups_options().into_iter().collect();
+        assert_eq!(option_map.get("media-source"), Some(&"manual"));
+        assert_eq!(option_map.get("media-type"), Some(&"labels"));
+
+        let tray = PrintOptions::new().media_source_tray(2);
+        assert_eq!(tray.as_cups_options(), vec![("media-source", "tray-2")]);
+        assert!(PrintOptions::new().media_source_tray(0).check().is_err());
+    }
+
+    #[test]
+    fn test_finishings() {
+        let options = PrintOptions::new()
+            .finishings(&[Finishing::StapleTopLeft, Finishing::Punch]);
+        assert_eq!(options.as_cups_options(), vec![("finishings", "20,5")]);
+
+        assert!(PrintOptions::new().finishings(&[]).is_empty());
+
+        for value in [3, 4, 5, 7, 10, 20, 31] {
+            assert_eq!(Finishing::from_ipp(value).map(Finishing::to_ipp), Some(value));
+        }
+        assert_eq!(Finishing::from_ipp(12), None);
+        assert_eq!(Finishing::from_ipp(0), None);
+    }
+
+    #[test]
+    fn test_number_up() {
+        let options = PrintOptions::new().number_up(4).number_up_layout("lrtb");
+        let option_map: std::collections::HashMap<&str, &str> =
+            options.as_cups_options().into_iter().collect();
+        assert_eq!(option_map.get("number-up"), Some(&"4"));
+        assert_eq!(option_map.get("number-up-layout"), Some(&"lrtb"));
+
+        assert!(options.check().is_ok());
+        assert!(PrintOptions::new().number_up(3).check().is_err());
+        assert!(PrintOptions::new().number_up_layout("diagonal").check().is_err());
+    }
+
+    #[test]
+    fn test_text_options() {
+        let options = PrintOptions::new()
+            .chars_per_inch(12.0)
+            .lines_per_inch(7.5)
+            .columns(2)
+            .page_border("double-thick");
+        let option_map: std::collections::HashMap<&str, &str> =
+            options.as_cups_options().into_iter().collect();
+        assert_eq!(option_map.get("cpi"), Some(&"12"));
+        assert_eq!(option_map.get("lpi"), Some(&"7.5"));
+        assert_eq!(option_map.get("columns"), Some(&"2"));
+        assert_eq!(option_map.get("page-border"), Some(&"double-thick"));
+
+        assert!(options.check().is_ok());
+        assert!(PrintOptions::new().chars_per_inch(0.0).check().is_err());
+        assert!(PrintOptions::new().lines_per_inch(-6.0).check().is_err());
+        assert!(PrintOptions::new().lines_per_inch(f32::NAN).check().is_err());
+        assert!(PrintOptions::new().columns(0).check().is_err());
+        assert!(PrintOptions::new().page_border("triple").check().is_err());
+    }
+
+    #[test]
+    fn test_release_pin() {
+        let options = PrintOptions::new().release_pin("4711");
+        assert_eq!(options.get(JOB_PASSWORD), Some("4711"));
+        assert_eq!(options.get(JOB_PASSWORD_ENCRYPTION), Some("none"));
+
+        assert!(options.check().is_ok());
+
+        for pin in [String::new(), "9".repeat(256)] {
+            let rejected = PrintOptions::new().release_pin(&pin);
+            assert_eq!(rejected.get(JOB_PASSWORD), None);
+            assert!(matches!(rejected.check(), Err(Error::ConfigurationError(_))));
+        }
+
+        // A rejected PIN survives merging into other options
+        let merged = PrintOptions::new().copies(2).merge(PrintOptions::new().release_pin(""));
+        assert!(merged.check().is_err());
+    }
+
+    #[test]
+    fn test_copies_in_range() {
+        assert!(copies_in_range("1", Some((1, 999))));
+        assert!(copies_in_range("999", Some((1, 999))));
+        assert!(!copies_in_range("10000", Some((1, 999))));
+        assert!(!copies_in_range("0", Some((1, 999))));
+        assert!(copies_in_range("10000", None));
+        assert!(!copies_in_range("many", None));
+    }
+
+    #[test]
+    fn test_collate_and_reverse_order() {
+        let options = PrintOptions::new().copies(2).collate(true).reverse_order(true);
+        assert_eq!(
+            options.get(MULTIPLE_DOCUMENT_HANDLING),
+            Some("separate-documents-collated-copies")
+        );
+        assert_eq!(options.get(PAGE_DELIVERY), Some("reverse-order"));
+
+        let options = options.collate(false).reverse_order(false);
+        assert_eq!(
+            options.get(MULTIPLE_DOCUMENT_HANDLING),
+            Some("separate-documents-uncollated-copies")
+        );
+        assert_eq!(options.get(PAGE_DELIVERY), Some("same-order"));
+    }
+
+    #[test]
+    fn test_page_ranges() {
+        let options = PrintOptions::new().page_ranges(&[(1, 4), (7, 7), (10, 12)]);
+        assert_eq!(options.as_cups_options(), vec![("page-ranges", "1-4,7,10-12")]);
+
+        // Overlapping, descending, reversed and zero-based ranges are rejected
+        for ranges in [
+            &[(1, 5), (4, 8)][..],
+            &[(7, 9), (1, 2)][..],
+            &[(5, 3)][..],
+            &[(0, 3)][..],
+        ] {
+            let rejected = PrintOptions::new().page_ranges(ranges);
+            assert_eq!(rejected.get(PAGE_RANGES), None);
+            assert!(matches!(rejected.check(), Err(Error::ConfigurationError(_))));
+        }
+
+        let empty = PrintOptions::new().page_ranges(&[]);
+        assert!(empty.is_empty());
+        assert!(empty.check().is_ok());
+    }
+
+    #[test]
+    fn test_scheduling_options() {
+        let options = PrintOptions::new()
+            .priority(75)
+            .hold_until(crate::JOB_HOLD_UNTIL_NIGHT);
+
+        let option_map: std::collections::HashMap<&str, &str> =
+            options.as_cups_options().into_iter().collect();
+        assert_eq!(option_map.get("job-priority"), Some(&"75"));
+        assert_eq!(option_map.get("job-hold-until"), Some(&"night"));
+
+        let clamped = PrintOptions::new().priority(0);
+        assert_eq!(clamped.as_cups_options(), vec![("job-priority", "1")]);
+
+        let clamped = PrintOptions::new().priority(500);
+        assert_eq!(clamped.as_cups_options(), vec![("job-priority", "100")]);
+    }
+
+    #[test]
+    fn test_enum_from_cups_value() {
+        for mode in [ColorMode::Auto, ColorMode::Color, ColorMode::Monochrome] {
+            assert_eq!(ColorMode::from_cups_value(&mode.to_string()), Some(mode));
+        }
+        for quality in [PrintQuality::Draft, PrintQuality::Normal, PrintQuality::High] {
+            assert_eq!(PrintQuality::from_cups_value(&quality.to_string()), Some(quality));
+        }
+        for duplex in [
+            DuplexMode::OneSided,
+            DuplexMode::TwoSidedPortrait,
+            DuplexMode::TwoSidedLandscape,
+        ] {
+            assert_eq!(DuplexMode::from_cups_value(&duplex.to_string()), Some(duplex));
+        }
+        for orientation in [Orientation::Portrait, Orientation::Landscape] {
+            assert_eq!(Orientation::from_cups_value(&orientation.to_string()), Some(orientation));
+        }
+
+        assert_eq!(
+            DuplexMode::from_cups_value("two-sided-long-edge"),
+            Some(DuplexMode::TwoSidedPortrait)
+        );
+        assert_eq!(PrintQuality::from_cups_value("5"), Some(PrintQuality::High));
+        assert_eq!(ColorMode::from_cups_value("sepia"), None);
+        assert_eq!(PrintQuality::from_cups_value("High"), None);
+        assert_eq!(DuplexMode::from_cups_value(""), None);
+        assert_eq!(Orientation::from_cups_value("6"), None);
+    }
+
     #[test]
     fn test_enum_to_string_conversions() {
         assert_eq!(ColorMode::Auto.to_string(), "auto");
@@ -203,5 +1002,11 @@ mod tests {
 
         assert_eq!(Orientation::Portrait.to_string(), "3");
         assert_eq!(Orientation::Landscape.to_string(), "4");
+
+        assert_eq!(Scaling::Auto.to_string(), "auto");
+        assert_eq!(Scaling::AutoFit.to_string(), "auto-fit");
+        assert_eq!(Scaling::Fill.to_string(), "fill");
+        assert_eq!(Scaling::Fit.to_string(), "fit");
+        assert_eq!(Scaling::None.to_string(), "none");
     }
 }
\ No newline at end of file
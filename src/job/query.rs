@@ -0,0 +1,243 @@
+use super::management::{JOB_ATTRIBUTES, job_info_from_attributes, printer_uri};
+use super::status::{JobFilter, JobInfo};
+use crate::config::get_user;
+use crate::connection::{HttpConnection, assemble_uri};
+use crate::destination::Destination;
+use crate::error::Result;
+use crate::ipp::{IppOperation, IppRequest, IppTag, IppValueTag};
+use std::ptr;
+
+/// Builder for Get-Jobs requests with custom filters and attributes
+///
+/// The flexible counterpart to [`get_jobs`](super::get_jobs): choose which
+/// jobs to list, cap the number returned and fetch extra attributes such as
+/// `job-media-sheets-completed`, which end up in [`JobInfo::extra`].
+///
+/// # Example
+///
+/// ```no_run
+/// use cups_rs::{JobFilter, JobQuery};
+///
+/// let jobs = JobQuery::new(Some("Office"))
+///     .which_jobs(JobFilter::Completed)
+///     .limit(20)
+///     .requested_attributes(&["job-media-sheets-completed"])
+///     .run()?;
+///
+/// for job in jobs {
+///     println!("{}: {:?}", job.id, job.extra.get("job-media-sheets-completed"));
+/// }
+/// # Ok::<(), cups_rs::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct JobQuery {
+    dest_name: Option<String>,
+    printer_uri: Option<String>,
+    which_jobs: JobFilter,
+    limit: Option<u32>,
+    my_jobs: bool,
+    requested_attributes: Vec<String>,
+}
+
+impl JobQuery {
+    /// Start a query for jobs on `dest_name`, or on every destination with `None`
+    ///
+    /// By default all jobs of all users are listed with the standard
+    /// `JobInfo` attributes.
+    pub fn new(dest_name: Option<&str>) -> Self {
+        JobQuery {
+            dest_name: dest_name.map(str::to_string),
+            printer_uri: None,
+            which_jobs: JobFilter::All,
+            limit: None,
+            my_jobs: false,
+            requested_attributes: Vec::new(),
+        }
+    }
+
+    /// Start a query for jobs on `dest`, addressed by the printer's own URI
+    ///
+    /// Prefer this over [`JobQuery::new`] with [`JobQuery::run_with_connection`]
+    /// against IPP servers other than the local scheduler, which don't know the
+    /// printer as `ipp://localhost/printers/<name>`.
+    pub fn for_destination(dest: &Destination) -> Result<Self> {
+        let mut query = JobQuery::new(Some(&dest.name));
+        query.printer_uri = Some(dest.printer_uri()?);
+        Ok(query)
+    }
+
+    /// Select which jobs to list
+    pub fn which_jobs(mut self, filter: JobFilter) -> Self {
+        self.which_jobs = filter;
+        self
+    }
+
+    /// Return at most `limit` jobs
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Only list jobs owned by the current CUPS user
+    pub fn my_jobs(mut self, my_jobs: bool) -> Self {
+        self.my_jobs = my_jobs;
+        self
+    }
+
+    /// Fetch only these attributes instead of the standard `JobInfo` set
+    ///
+    /// `job-id` is always requested. Attributes that `JobInfo` has no field
+    /// for are returned in [`JobInfo::extra`]; fields whose attribute was not
+    /// requested are left empty.
+    pub fn requested_attributes(mut self, attributes: &[&str]) -> Self {
+        self.requested_attributes = attributes.iter().map(|a| a.to_string()).collect();
+        self
+    }
+
+    /// Send the query to the default CUPS server
+    pub fn run(&self) -> Result<Vec<JobInfo>> {
+        self.run_on(ptr::null_mut())
+    }
+
+    /// Send the query over an existing connection
+    pub fn run_with_connection(&self, connection: &HttpConnection) -> Result<Vec<JobInfo>> {
+        self.run_on(connection.checked_ptr()?)
+    }
+
+    fn run_on(&self, http: *mut crate::bindings::_http_s) -> Result<Vec<JobInfo>> {
+        let response = self
+            .build_request()?
+            .send_with_http(http, "/")?
+            .into_result()?;
+
+        Ok(response
+            .groups()
+            .into_iter()
+            .filter(|(tag, _)| *tag == IppTag::Job)
+            .filter_map(|(_, attributes)| job_info_from_attributes(&attributes))
+            .collect())
+    }
+
+    fn build_request(&self) -> Result<IppRequest> {
+        let uri = match (&self.printer_uri, &self.dest_name) {
+            (Some(uri), _) => uri.clone(),
+            (None, Some(name)) => printer_uri(name)?,
+            (None, None) => assemble_uri("ipp", "localhost", 0, "/")?,
+        };
+
+        let mut request = IppRequest::new(IppOperation::GetJobs)?;
+        request.add_string(IppTag::Operation, IppValueTag::Uri, "printer-uri", &uri)?;
+        request.add_string(
+            IppTag::Operation,
+            IppValueTag::Name,
+            "requesting-user-name",
+            &get_user(),
+        )?;
+        request.add_string(
+            IppTag::Operation,
+            IppValueTag::Keyword,
+            "which-jobs",
+            which_jobs_keyword(self.which_jobs),
+        )?;
+
+        if self.my_jobs {
+            request.add_boolean(IppTag::Operation, "my-jobs", true)?;
+        }
+
+        if let Some(limit) = self.limit {
+            request.add_integer(
+                IppTag::Operation,
+                IppValueTag::Integer,
+                "limit",
+                limit.min(i32::MAX as u32) as i32,
+            )?;
+        }
+
+        request.add_strings(
+            IppTag::Operation,
+            IppValueTag::Keyword,
+            "requested-attributes",
+            &self.attribute_list(),
+        )?;
+
+        Ok(request)
+    }
+
+    fn attribute_list(&self) -> Vec<&str> {
+        if self.requested_attributes.is_empty() {
+            return JOB_ATTRIBUTES.to_vec();
+        }
+
+        let mut attributes = vec!["job-id"];
+        for attribute in &self.requested_attributes {
+            if !attributes.contains(&attribute.as_str()) {
+                attributes.push(attribute);
+            }
+        }
+        attributes
+    }
+}
+
+/// Map a [`JobFilter`] to its IPP `which-jobs` keyword
+fn which_jobs_keyword(filter: JobFilter) -> &'static str {
+    match filter {
+        JobFilter::All => "all",
+        JobFilter::Active => "not-completed",
+        JobFilter::Completed => "completed",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_which_jobs_keyword() {
+        assert_eq!(which_jobs_keyword(JobFilter::All), "all");
+        assert_eq!(which_jobs_keyword(JobFilter::Active), "not-completed");
+        assert_eq!(which_jobs_keyword(JobFilter::Completed), "completed");
+    }
+
+    #[test]
+    fn test_job_query_attribute_list() {
+        let query = JobQuery::new(None);
+        assert_eq!(query.attribute_list(), JOB_ATTRIBUTES.to_vec());
+
+        let query = JobQuery::new(Some("Office"))
+            .requested_attributes(&["job-media-sheets-completed", "job-id", "job-name"]);
+        assert_eq!(
+            query.attribute_list(),
+            vec!["job-id", "job-media-sheets-completed", "job-name"]
+        );
+    }
+
+    #[test]
+    fn test_job_query_request() {
+        let request = JobQuery::new(Some("Office"))
+            .which_jobs(JobFilter::Completed)
+            .limit(5)
+            .my_jobs(true)
+            .build_request()
+            .unwrap();
+        assert!(!request.as_ptr().is_null());
+    }
+
+    #[test]
+    fn test_job_query_for_destination() {
+        let dest = Destination::new("Office")
+            .with_option("printer-uri-supported", "ipp://print.example.com/ipp/print");
+        let query = JobQuery::for_destination(&dest).unwrap();
+        assert_eq!(query.dest_name.as_deref(), Some("Office"));
+        assert_eq!(
+            query.printer_uri.as_deref(),
+            Some("ipp://print.example.com/ipp/print")
+        );
+        assert!(query.build_request().is_ok());
+
+        let query = JobQuery::for_destination(&Destination::new("Büro")).unwrap();
+        assert_eq!(
+            query.printer_uri.as_deref(),
+            Some("ipp://localhost/printers/B%C3%BCro")
+        );
+    }
+}
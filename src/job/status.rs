@@ -1,6 +1,10 @@
+use crate::constants::{WHICHJOBS_ACTIVE, WHICHJOBS_ALL, WHICHJOBS_COMPLETED};
+use std::collections::HashMap;
 use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum JobStatus {
     Pending,
     Processing,
@@ -13,6 +17,10 @@ pub enum JobStatus {
 }
 
 impl JobStatus {
+    /// Convert a CUPS `ipp_jstate_t` value
+    ///
+    /// The mapping uses the constants generated from the installed CUPS
+    /// headers, so it never relies on hard-coded state numbers.
     pub fn from_cups_state(state: i32) -> Self {
         match state as u32 {
             crate::bindings::ipp_jstate_e_IPP_JSTATE_PENDING => JobStatus::Pending,
@@ -26,6 +34,7 @@ impl JobStatus {
         }
     }
 
+    /// Convert back to the CUPS `ipp_jstate_t` value (0 for `Unknown`)
     pub fn to_cups_value(&self) -> i32 {
         match self {
             JobStatus::Pending => crate::bindings::ipp_jstate_e_IPP_JSTATE_PENDING as i32,
@@ -38,6 +47,14 @@ impl JobStatus {
             JobStatus::Unknown => 0,
         }
     }
+
+    /// Returns true if the job has finished and will not change state again
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            JobStatus::Completed | JobStatus::Canceled | JobStatus::Aborted
+        )
+    }
 }
 
 impl fmt::Display for JobStatus {
@@ -55,7 +72,32 @@ impl fmt::Display for JobStatus {
     }
 }
 
+/// Which jobs to list; the typed form of the `WHICHJOBS_*` constants
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JobFilter {
+    /// Every job, active or finished
+    #[default]
+    All,
+    /// Jobs that are pending, held or processing
+    Active,
+    /// Jobs that have completed, been canceled or aborted
+    Completed,
+}
+
+impl JobFilter {
+    /// Convert to the `which` value expected by `cupsGetJobs2`
+    pub fn to_cups(self) -> i32 {
+        match self {
+            JobFilter::All => WHICHJOBS_ALL,
+            JobFilter::Active => WHICHJOBS_ACTIVE,
+            JobFilter::Completed => WHICHJOBS_COMPLETED,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct JobInfo {
     pub id: i32,
     pub title: String,
@@ -67,6 +109,65 @@ pub struct JobInfo {
     pub creation_time: i64,
     pub processing_time: i64,
     pub completed_time: i64,
+    /// Reasons for the current state, e.g. `job-hold-until-specified`
+    ///
+    /// Only available when the job is queried individually with `get_job_info`.
+    pub state_reasons: Vec<String>,
+    /// Additional attributes requested with [`JobQuery::requested_attributes`](super::JobQuery::requested_attributes)
+    ///
+    /// Multi-valued attributes are joined with commas.
+    pub extra: HashMap<String, String>,
+}
+
+impl JobInfo {
+    /// Get the time the job was created
+    pub fn creation_datetime(&self) -> Option<SystemTime> {
+        epoch_to_system_time(self.creation_time)
+    }
+
+    /// Get the time the job started processing, if it has
+    pub fn processing_datetime(&self) -> Option<SystemTime> {
+        epoch_to_system_time(self.processing_time)
+    }
+
+    /// Get the time the job completed, if it has
+    pub fn completed_datetime(&self) -> Option<SystemTime> {
+        epoch_to_system_time(self.completed_time)
+    }
+
+    /// Get the time the job was created as a UTC timestamp
+    #[cfg(feature = "chrono")]
+    pub fn creation_utc(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        epoch_to_utc(self.creation_time)
+    }
+
+    /// Get the time the job started processing as a UTC timestamp
+    #[cfg(feature = "chrono")]
+    pub fn processing_utc(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        epoch_to_utc(self.processing_time)
+    }
+
+    /// Get the time the job completed as a UTC timestamp
+    #[cfg(feature = "chrono")]
+    pub fn completed_utc(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        epoch_to_utc(self.completed_time)
+    }
+}
+
+/// CUPS reports unset job times as 0 seconds since the epoch
+fn epoch_to_system_time(seconds: i64) -> Option<SystemTime> {
+    if seconds <= 0 {
+        return None;
+    }
+    UNIX_EPOCH.checked_add(Duration::from_secs(seconds as u64))
+}
+
+#[cfg(feature = "chrono")]
+fn epoch_to_utc(seconds: i64) -> Option<chrono::DateTime<chrono::Utc>> {
+    if seconds <= 0 {
+        return None;
+    }
+    chrono::DateTime::from_timestamp(seconds, 0)
 }
 
 #[cfg(test)]
@@ -99,6 +200,37 @@ mod tests {
         assert_eq!(JobStatus::Unknown.to_cups_value(), 0);
     }
 
+    #[test]
+    fn test_job_status_round_trip() {
+        let known = [
+            JobStatus::Pending,
+            JobStatus::Processing,
+            JobStatus::Completed,
+            JobStatus::Canceled,
+            JobStatus::Aborted,
+            JobStatus::Held,
+            JobStatus::Stopped,
+        ];
+
+        for status in known {
+            assert_eq!(JobStatus::from_cups_state(status.to_cups_value()), status);
+        }
+        assert_eq!(JobStatus::from_cups_state(JobStatus::Unknown.to_cups_value()), JobStatus::Unknown);
+        assert_eq!(JobStatus::from_cups_state(-1), JobStatus::Unknown);
+    }
+
+    #[test]
+    fn test_job_status_is_terminal() {
+        assert!(JobStatus::Completed.is_terminal());
+        assert!(JobStatus::Canceled.is_terminal());
+        assert!(JobStatus::Aborted.is_terminal());
+        assert!(!JobStatus::Pending.is_terminal());
+        assert!(!JobStatus::Processing.is_terminal());
+        assert!(!JobStatus::Held.is_terminal());
+        assert!(!JobStatus::Stopped.is_terminal());
+        assert!(!JobStatus::Unknown.is_terminal());
+    }
+
     #[test]
     fn test_job_status_display() {
         assert_eq!(JobStatus::Pending.to_string(), "Pending");
@@ -111,6 +243,14 @@ mod tests {
         assert_eq!(JobStatus::Unknown.to_string(), "Unknown");
     }
 
+    #[test]
+    fn test_job_filter_to_cups() {
+        assert_eq!(JobFilter::All.to_cups(), WHICHJOBS_ALL);
+        assert_eq!(JobFilter::Active.to_cups(), WHICHJOBS_ACTIVE);
+        assert_eq!(JobFilter::Completed.to_cups(), WHICHJOBS_COMPLETED);
+        assert_eq!(JobFilter::default(), JobFilter::All);
+    }
+
     #[test]
     fn test_job_info_creation() {
         let job_info = JobInfo {
@@ -124,10 +264,48 @@ mod tests {
             creation_time: 1640995200,
             processing_time: 1640995260,
             completed_time: 0,
+            state_reasons: vec!["job-printing".to_string()],
+            extra: HashMap::new(),
         };
 
         assert_eq!(job_info.id, 123);
         assert_eq!(job_info.title, "Test Job");
         assert_eq!(job_info.status, JobStatus::Processing);
+        assert_eq!(job_info.state_reasons, vec!["job-printing".to_string()]);
+    }
+
+    #[test]
+    fn test_job_info_datetimes() {
+        let job_info = JobInfo {
+            id: 7,
+            title: "Test Job".to_string(),
+            user: "testuser".to_string(),
+            dest: "TestPrinter".to_string(),
+            status: JobStatus::Processing,
+            size: 1024,
+            priority: 50,
+            creation_time: 1640995200,
+            processing_time: 1640995260,
+            completed_time: 0,
+            state_reasons: Vec::new(),
+            extra: HashMap::new(),
+        };
+
+        assert_eq!(
+            job_info.creation_datetime(),
+            Some(UNIX_EPOCH + Duration::from_secs(1640995200))
+        );
+        assert_eq!(
+            job_info.processing_datetime(),
+            Some(UNIX_EPOCH + Duration::from_secs(1640995260))
+        );
+        assert_eq!(job_info.completed_datetime(), None);
+
+        #[cfg(feature = "chrono")]
+        {
+            let created = job_info.creation_utc().unwrap();
+            assert_eq!(created.to_rfc3339(), "2022-01-01T00:00:00+00:00");
+            assert!(job_info.completed_utc().is_none());
+        }
     }
 }
@@ -67,6 +67,8 @@
 //! - [`ipp`]: Low-level IPP (Internet Printing Protocol) request/response handling
 //! - [`job`]: Print job creation, submission, and management
 //! - [`options`]: Print option parsing, encoding, and manipulation
+//! - [`subscription`]: IPP event subscriptions for job and printer changes
+//! - [`units`]: Conversions for the hundredths-of-millimeter media unit
 //! - [`Error`] and [`Result`]: Error types and result handling
 //!
 //! ## API Coverage
@@ -164,6 +166,30 @@ pub mod ipp;
 /// - Rich print options (copies, color, duplex, media, orientation)
 pub mod job;
 
+/// Retrying operations that fail with transient errors
+///
+/// Re-runs a closure with exponential backoff while it fails with an error
+/// that `Error::is_recoverable` classifies as temporary.
+pub mod retry;
+
+/// Temporary spool files and file-device output
+///
+/// Helpers for the "print to a file and read it back" workflow:
+/// - Stage print data in a CUPS temporary file
+/// - Find where a `file:` device printer writes its output
+pub mod spool;
+
+/// Conversions between CUPS media units, millimeters, inches and points
+pub mod units;
+
+/// IPP event subscriptions for job and printer state changes
+///
+/// Event-driven alternative to polling job status:
+/// - Subscribe to job and printer events on a destination
+/// - Fetch pending notifications with the `ippget` pull method
+/// - Subscriptions are cancelled automatically when dropped
+pub mod subscription;
+
 /// Print option parsing, encoding, and manipulation
 ///
 /// Utilities for working with CUPS print options:
@@ -175,21 +201,30 @@ pub mod job;
 pub mod options;
 
 pub use constants::*;
-pub use connection::{ConnectionFlags, HttpConnection, connect_to_destination};
+pub use connection::{ConnectionFlags, HttpConnection, WeakHttpConnection, connect_to_destination};
 pub use destination::{
-    Destination, DestinationInfo, Destinations, MediaSize, PrinterState, OptionConflict, copy_dest,
-    enum_destinations, find_destinations, get_all_destinations, get_default_destination,
-    get_destination, remove_dest,
+    Destination, DestinationInfo, DestinationRef, DestinationStream, Destinations,
+    DestinationsIter, MediaSize, OptionConflict, PrinterCapabilities, PrinterDefaults, PrinterState,
+    RawDest, Severity, StateReason,
+    copy_dest, discover_destinations, enum_destinations, enumerate_destinations,
+    find_destinations, get_all_destinations, get_all_destinations_timeout,
+    get_default_destination, get_destination, get_destination_instance, get_named_destination,
+    remove_dest, remove_ppd,
 };
 pub use error::{Error, ErrorCategory, Result};
 pub use job::{
-    ColorMode, DuplexMode, FORMAT_JPEG, FORMAT_PDF, FORMAT_POSTSCRIPT, FORMAT_TEXT, JobInfo,
-    JobStatus, Orientation, PrintOptions, PrintQuality, cancel_job, create_job,
-    create_job_with_options, get_active_jobs, get_completed_jobs, get_job_info, get_jobs,
+    ColorMode, DuplexMode, FORMAT_GIF, FORMAT_JPEG, FORMAT_PDF, FORMAT_PNG, FORMAT_POSTSCRIPT,
+    FORMAT_RAW, FORMAT_TEXT, FORMAT_TIFF, Finishing, Job, JobFilter, JobInfo, JobQuery, JobStatus,
+    Orientation, PrintOptions, PrintQuality, Resolution, ResolutionUnit, Scaling,
+    cancel_all_jobs, cancel_job, create_job, create_job_with_connection,
+    create_job_with_document_uri, create_job_with_options,
+    get_active_jobs, get_completed_jobs, get_job_info, get_jobs, get_jobs_filtered,
+    get_jobs_with_connection, get_my_jobs, get_recent_jobs, move_job, print_data, print_file,
 };
 pub use ipp::{
     IppAttribute, IppOperation, IppRequest, IppResponse, IppStatus, IppTag, IppValueTag,
 };
+pub use subscription::{Notification, Subscription, SubscriptionEvent};
 pub use options::{
     add_integer_option, add_option, encode_option, encode_options, encode_options_with_group,
     get_integer_option, get_option, parse_options, remove_option,
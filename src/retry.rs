@@ -0,0 +1,94 @@
+use crate::error::Result;
+use std::thread;
+use std::time::Duration;
+
+/// Run `operation`, retrying it while it fails with a recoverable error
+///
+/// The operation is attempted up to `max_attempts` times (at least once).
+/// After each failure for which [`Error::is_recoverable`](crate::Error::is_recoverable)
+/// is true, the thread sleeps before the next attempt, starting at `backoff`
+/// and doubling every time. Non-recoverable errors, and the error from the
+/// last attempt, are returned immediately.
+///
+/// # Example
+///
+/// ```no_run
+/// use cups_rs::{create_job, get_destination, retry::with_retry};
+/// use std::time::Duration;
+///
+/// let printer = get_destination("Office")?;
+/// let job = with_retry(3, Duration::from_millis(500), || {
+///     create_job(&printer, "Quarterly Report")
+/// })?;
+/// println!("Created job {}", job.id);
+/// # Ok::<(), cups_rs::Error>(())
+/// ```
+pub fn with_retry<T, F>(max_attempts: u32, backoff: Duration, mut operation: F) -> Result<T>
+where
+    F: FnMut() -> Result<T>,
+{
+    let max_attempts = max_attempts.max(1);
+    let mut delay = backoff;
+    let mut attempt = 1;
+
+    loop {
+        match operation() {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < max_attempts && e.is_recoverable() => {
+                thread::sleep(delay);
+                delay = delay.saturating_mul(2);
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+
+    #[test]
+    fn test_retry_until_success() {
+        let mut calls = 0;
+        let result = with_retry(3, Duration::ZERO, || {
+            calls += 1;
+            if calls < 3 {
+                Err(Error::ServerUnavailable)
+            } else {
+                Ok(calls)
+            }
+        });
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[test]
+    fn test_retry_gives_up() {
+        let mut calls = 0;
+        let result: Result<()> = with_retry(2, Duration::ZERO, || {
+            calls += 1;
+            Err(Error::Timeout)
+        });
+        assert!(matches!(result, Err(Error::Timeout)));
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn test_retry_stops_on_permanent_error() {
+        let mut calls = 0;
+        let result: Result<()> = with_retry(5, Duration::ZERO, || {
+            calls += 1;
+            Err(Error::PermissionDenied("Office".to_string()))
+        });
+        assert!(matches!(result, Err(Error::PermissionDenied(_))));
+        assert_eq!(calls, 1);
+
+        calls = 0;
+        let _: Result<()> = with_retry(0, Duration::ZERO, || {
+            calls += 1;
+            Err(Error::Timeout)
+        });
+        assert_eq!(calls, 1);
+    }
+}
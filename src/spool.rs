@@ -0,0 +1,90 @@
+use crate::bindings;
+use crate::destination::Destination;
+use crate::error::{Error, Result};
+use std::ffi::CStr;
+use std::fs::File;
+use std::os::raw::c_char;
+use std::os::unix::io::FromRawFd;
+use std::path::PathBuf;
+
+const TEMP_FILENAME_LEN: usize = 1024;
+
+/// Create a temporary file in the CUPS temporary directory
+///
+/// Wraps `cupsTempFd`, which creates the file in `TMPDIR` (or the CUPS
+/// default) with permissions restricted to the current user. The file is
+/// opened for reading and writing and is **not** removed automatically;
+/// delete it with `std::fs::remove_file` once the data has been printed.
+///
+/// # Example
+///
+/// ```no_run
+/// use cups_rs::{get_destination, print_file, spool, PrintOptions};
+/// use std::io::Write;
+///
+/// let (mut file, path) = spool::create_temp_file()?;
+/// file.write_all(b"Staged print data\n")?;
+/// drop(file);
+///
+/// let printer = get_destination("PDF")?;
+/// let job = print_file(&printer, &path, &PrintOptions::new())?;
+/// println!("Submitted job {}", job.id);
+/// std::fs::remove_file(&path)?;
+/// # Ok::<(), cups_rs::Error>(())
+/// ```
+pub fn create_temp_file() -> Result<(File, PathBuf)> {
+    let mut buffer = [0 as c_char; TEMP_FILENAME_LEN];
+    let fd = unsafe { bindings::cupsTempFd(buffer.as_mut_ptr(), TEMP_FILENAME_LEN as i32) };
+
+    if fd < 0 {
+        return Err(Error::IoError(std::io::Error::last_os_error()));
+    }
+
+    let path = unsafe { CStr::from_ptr(buffer.as_ptr()) }
+        .to_string_lossy()
+        .into_owned();
+    let file = unsafe { File::from_raw_fd(fd) };
+
+    Ok((file, PathBuf::from(path)))
+}
+
+/// Get the output path of a printer that uses a `file:` device
+///
+/// Printers created with e.g. `lpadmin -p TestPDF -E -v file:///tmp/out.pdf`
+/// write every job to the file or directory named in their `device-uri`.
+/// Returns `None` for printers with any other kind of device, or when the
+/// device URI isn't visible to the current user.
+///
+/// Pair this with [`crate::Job::wait_for_completion`] to read back what a
+/// test job produced.
+pub fn file_device_path(dest: &Destination) -> Option<PathBuf> {
+    let uri = dest.device_uri()?;
+    let path = uri
+        .strip_prefix("file://")
+        .or_else(|| uri.strip_prefix("file:"))?;
+
+    if path.is_empty() {
+        None
+    } else {
+        Some(PathBuf::from(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_device_path() {
+        let dest = Destination::new("TestPDF").with_option("device-uri", "file:///tmp/out.pdf");
+        assert_eq!(file_device_path(&dest), Some(PathBuf::from("/tmp/out.pdf")));
+
+        let dest = Destination::new("TestPDF").with_option("device-uri", "file:/dev/null");
+        assert_eq!(file_device_path(&dest), Some(PathBuf::from("/dev/null")));
+
+        let dest = Destination::new("Office").with_option("device-uri", "ipp://printer/ipp/print");
+        assert_eq!(file_device_path(&dest), None);
+
+        assert_eq!(file_device_path(&Destination::new("Bare")), None);
+    }
+}
@@ -0,0 +1,325 @@
+use crate::config::get_user;
+use crate::connection::{ConnectionFlags, HttpConnection};
+use crate::destination::{Destination, PrinterState};
+use crate::error::{Error, Result};
+use crate::error_helpers::cups_error_to_our_error;
+use crate::ipp::{IppAttribute, IppOperation, IppRequest, IppTag, IppValueTag};
+use crate::job::JobStatus;
+use std::cell::Cell;
+use std::fmt;
+use std::time::Duration;
+
+const SUBSCRIPTION_CONNECT_TIMEOUT_MS: i32 = 30_000;
+
+/// Events a subscription can be notified about
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SubscriptionEvent {
+    /// A job was created
+    JobCreated,
+    /// A job finished, was canceled or was aborted
+    JobCompleted,
+    /// A job changed state, including creation and completion
+    JobStateChanged,
+    /// A job printed another page
+    JobProgress,
+    /// The printer changed state
+    PrinterStateChanged,
+    /// The printer was stopped
+    PrinterStopped,
+    /// The printer's attributes were changed
+    PrinterConfigChanged,
+    /// A printer was added
+    PrinterAdded,
+    /// A printer was deleted
+    PrinterDeleted,
+    /// Any other `notify-events` keyword
+    Other(String),
+}
+
+impl SubscriptionEvent {
+    /// Get the `notify-events` keyword for this event
+    pub fn as_str(&self) -> &str {
+        match self {
+            SubscriptionEvent::JobCreated => "job-created",
+            SubscriptionEvent::JobCompleted => "job-completed",
+            SubscriptionEvent::JobStateChanged => "job-state-changed",
+            SubscriptionEvent::JobProgress => "job-progress",
+            SubscriptionEvent::PrinterStateChanged => "printer-state-changed",
+            SubscriptionEvent::PrinterStopped => "printer-stopped",
+            SubscriptionEvent::PrinterConfigChanged => "printer-config-changed",
+            SubscriptionEvent::PrinterAdded => "printer-added",
+            SubscriptionEvent::PrinterDeleted => "printer-deleted",
+            SubscriptionEvent::Other(keyword) => keyword,
+        }
+    }
+
+    /// Parse a `notify-subscribed-event` keyword
+    pub fn from_keyword(keyword: &str) -> Self {
+        match keyword {
+            "job-created" => SubscriptionEvent::JobCreated,
+            "job-completed" => SubscriptionEvent::JobCompleted,
+            "job-state-changed" => SubscriptionEvent::JobStateChanged,
+            "job-progress" => SubscriptionEvent::JobProgress,
+            "printer-state-changed" => SubscriptionEvent::PrinterStateChanged,
+            "printer-stopped" => SubscriptionEvent::PrinterStopped,
+            "printer-config-changed" => SubscriptionEvent::PrinterConfigChanged,
+            "printer-added" => SubscriptionEvent::PrinterAdded,
+            "printer-deleted" => SubscriptionEvent::PrinterDeleted,
+            other => SubscriptionEvent::Other(other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for SubscriptionEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A single event delivered to a [`Subscription`]
+#[derive(Debug, Clone)]
+pub struct Notification {
+    /// Sequence number of this event within the subscription
+    pub sequence_number: i32,
+    /// The event that happened
+    pub event: SubscriptionEvent,
+    /// Human-readable description of the event
+    pub text: Option<String>,
+    /// Name of the printer the event is about
+    pub printer_name: Option<String>,
+    /// Printer state at the time of the event
+    pub printer_state: Option<PrinterState>,
+    /// ID of the job the event is about, for job events
+    pub job_id: Option<i32>,
+    /// Job state at the time of the event, for job events
+    pub job_state: Option<JobStatus>,
+}
+
+impl Notification {
+    fn from_attributes(attrs: &[IppAttribute]) -> Option<Self> {
+        let find = |name: &str| {
+            attrs
+                .iter()
+                .find(|attr| attr.name().as_deref() == Some(name))
+        };
+
+        let sequence_number = find("notify-sequence-number")?.get_integer(0);
+        let keyword = find("notify-subscribed-event")?.get_string(0)?;
+
+        Some(Notification {
+            sequence_number,
+            event: SubscriptionEvent::from_keyword(&keyword),
+            text: find("notify-text").and_then(|attr| attr.get_string(0)),
+            printer_name: find("printer-name").and_then(|attr| attr.get_string(0)),
+            printer_state: find("printer-state")
+                .map(|attr| PrinterState::from_ipp_code(attr.get_integer(0))),
+            job_id: find("notify-job-id").map(|attr| attr.get_integer(0)),
+            job_state: find("job-state")
+                .map(|attr| JobStatus::from_cups_state(attr.get_integer(0))),
+        })
+    }
+}
+
+/// A pull-based IPP event subscription on a printer
+///
+/// Created with `Create-Printer-Subscriptions` using the `ippget` pull method;
+/// call [`Subscription::get_notifications`] to fetch events that happened
+/// since the previous call. The subscription is cancelled on the server when
+/// dropped.
+pub struct Subscription {
+    id: i32,
+    dest_name: String,
+    printer_uri: String,
+    connection: HttpConnection,
+    next_sequence: Cell<i32>,
+    cancelled: bool,
+}
+
+impl Subscription {
+    /// Subscribe to events on a destination
+    ///
+    /// `lease` is how long the server keeps the subscription without it being
+    /// renewed; `None` uses the server default.
+    pub fn create(
+        dest: &Destination,
+        events: &[SubscriptionEvent],
+        lease: Option<Duration>,
+    ) -> Result<Self> {
+        if events.is_empty() {
+            return Err(Error::ConfigurationError(
+                "A subscription needs at least one event".to_string(),
+            ));
+        }
+
+        let connection = dest.connect(
+            ConnectionFlags::Scheduler,
+            Some(SUBSCRIPTION_CONNECT_TIMEOUT_MS),
+            None,
+        )?;
+        let printer_uri = dest.printer_uri()?;
+
+        let mut request = IppRequest::new(IppOperation::CreatePrinterSubscriptions)?;
+        add_target_attributes(&mut request, &printer_uri)?;
+        request.add_string(
+            IppTag::Subscription,
+            IppValueTag::Keyword,
+            "notify-pull-method",
+            "ippget",
+        )?;
+        let keywords: Vec<&str> = events.iter().map(|event| event.as_str()).collect();
+        request.add_strings(
+            IppTag::Subscription,
+            IppValueTag::Keyword,
+            "notify-events",
+            &keywords,
+        )?;
+        if let Some(lease) = lease {
+            request.add_integer(
+                IppTag::Subscription,
+                IppValueTag::Integer,
+                "notify-lease-duration",
+                lease.as_secs().min(i32::MAX as u64) as i32,
+            )?;
+        }
+
+        let response = request.send(&connection, "/")?;
+        if !response.is_successful() {
+            return Err(cups_error_to_our_error("create subscription", Some(&dest.name)));
+        }
+
+        let id = response
+            .find_attribute("notify-subscription-id", Some(IppTag::Subscription))
+            .map(|attr| attr.get_integer(0))
+            .ok_or_else(|| {
+                Error::ServerError("Server did not return a subscription ID".to_string())
+            })?;
+
+        Ok(Subscription {
+            id,
+            dest_name: dest.name.clone(),
+            printer_uri,
+            connection,
+            next_sequence: Cell::new(1),
+            cancelled: false,
+        })
+    }
+
+    /// Get the server-assigned subscription ID
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    /// Fetch the events that happened since the previous call
+    ///
+    /// Returns an empty list if nothing new has happened. Does not wait for
+    /// new events.
+    pub fn get_notifications(&self) -> Result<Vec<Notification>> {
+        let mut request = IppRequest::new(IppOperation::GetNotifications)?;
+        add_target_attributes(&mut request, &self.printer_uri)?;
+        request.add_integer(
+            IppTag::Operation,
+            IppValueTag::Integer,
+            "notify-subscription-ids",
+            self.id,
+        )?;
+        request.add_integer(
+            IppTag::Operation,
+            IppValueTag::Integer,
+            "notify-sequence-numbers",
+            self.next_sequence.get(),
+        )?;
+        request.add_boolean(IppTag::Operation, "notify-wait", false)?;
+
+        let response = request.send(&self.connection, "/")?;
+        if !response.is_successful() {
+            return Err(cups_error_to_our_error("get notifications", Some(&self.dest_name)));
+        }
+
+        let notifications: Vec<Notification> = response
+            .groups()
+            .iter()
+            .filter(|(tag, _)| *tag == IppTag::EventNotification)
+            .filter_map(|(_, attrs)| Notification::from_attributes(attrs))
+            .collect();
+
+        if let Some(last) = notifications.iter().map(|n| n.sequence_number).max() {
+            self.next_sequence.set(last + 1);
+        }
+
+        Ok(notifications)
+    }
+
+    /// Cancel the subscription on the server
+    ///
+    /// Unlike dropping the subscription, this reports whether it worked.
+    pub fn cancel(mut self) -> Result<()> {
+        self.cancelled = true;
+        self.cancel_on_server()
+    }
+
+    fn cancel_on_server(&self) -> Result<()> {
+        let mut request = IppRequest::new(IppOperation::CancelSubscription)?;
+        add_target_attributes(&mut request, &self.printer_uri)?;
+        request.add_integer(
+            IppTag::Operation,
+            IppValueTag::Integer,
+            "notify-subscription-id",
+            self.id,
+        )?;
+
+        let response = request.send(&self.connection, "/")?;
+        if response.is_successful() {
+            Ok(())
+        } else {
+            Err(cups_error_to_our_error("cancel subscription", Some(&self.dest_name)))
+        }
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        if self.cancelled {
+            return;
+        }
+        if let Err(e) = self.cancel_on_server() {
+            eprintln!("Warning: Failed to cancel subscription {}: {}", self.id, e);
+        }
+    }
+}
+
+fn add_target_attributes(request: &mut IppRequest, printer_uri: &str) -> Result<()> {
+    request.add_string(IppTag::Operation, IppValueTag::Uri, "printer-uri", printer_uri)?;
+    request.add_string(
+        IppTag::Operation,
+        IppValueTag::Name,
+        "requesting-user-name",
+        &get_user(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subscription_event_keywords() {
+        let events = [
+            SubscriptionEvent::JobCreated,
+            SubscriptionEvent::JobCompleted,
+            SubscriptionEvent::JobStateChanged,
+            SubscriptionEvent::JobProgress,
+            SubscriptionEvent::PrinterStateChanged,
+            SubscriptionEvent::PrinterStopped,
+            SubscriptionEvent::PrinterConfigChanged,
+            SubscriptionEvent::PrinterAdded,
+            SubscriptionEvent::PrinterDeleted,
+        ];
+        for event in events {
+            assert_eq!(SubscriptionEvent::from_keyword(event.as_str()), event);
+        }
+
+        let other = SubscriptionEvent::from_keyword("server-restarted");
+        assert_eq!(other, SubscriptionEvent::Other("server-restarted".to_string()));
+        assert_eq!(other.to_string(), "server-restarted");
+    }
+}
@@ -0,0 +1,66 @@
+//! CUPS reports media dimensions and margins in hundredths of millimeters
+//! (2540 per inch). These helpers convert between that unit, millimeters,
+//! inches and PostScript points.
+
+/// Hundredths of a millimeter in one inch
+pub const HUNDREDTHS_MM_PER_INCH: f64 = 2540.0;
+
+/// Hundredths of a millimeter in one millimeter
+pub const HUNDREDTHS_MM_PER_MM: f64 = 100.0;
+
+/// PostScript points in one inch
+pub const POINTS_PER_INCH: f64 = 72.0;
+
+/// Convert hundredths of millimeters to inches
+pub fn hundredths_mm_to_inches(value: i32) -> f64 {
+    value as f64 / HUNDREDTHS_MM_PER_INCH
+}
+
+/// Convert hundredths of millimeters to millimeters
+pub fn hundredths_mm_to_mm(value: i32) -> f64 {
+    value as f64 / HUNDREDTHS_MM_PER_MM
+}
+
+/// Convert hundredths of millimeters to PostScript points
+pub fn hundredths_mm_to_points(value: i32) -> f64 {
+    hundredths_mm_to_inches(value) * POINTS_PER_INCH
+}
+
+/// Convert inches to hundredths of millimeters, rounding to the nearest unit
+pub fn inches_to_hundredths_mm(value: f64) -> i32 {
+    (value * HUNDREDTHS_MM_PER_INCH).round() as i32
+}
+
+/// Convert millimeters to hundredths of millimeters, rounding to the nearest unit
+pub fn mm_to_hundredths_mm(value: f64) -> i32 {
+    (value * HUNDREDTHS_MM_PER_MM).round() as i32
+}
+
+/// Convert PostScript points to hundredths of millimeters, rounding to the nearest unit
+pub fn points_to_hundredths_mm(value: f64) -> i32 {
+    inches_to_hundredths_mm(value / POINTS_PER_INCH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unit_conversions() {
+        assert!((hundredths_mm_to_inches(21590) - 8.5).abs() < 1e-9);
+        assert!((hundredths_mm_to_mm(21000) - 210.0).abs() < 1e-9);
+        assert!((hundredths_mm_to_points(2540) - 72.0).abs() < 1e-9);
+
+        assert_eq!(inches_to_hundredths_mm(11.0), 27940);
+        assert_eq!(mm_to_hundredths_mm(215.9), 21590);
+        assert_eq!(points_to_hundredths_mm(612.0), 21590);
+    }
+
+    #[test]
+    fn test_unit_round_trip() {
+        for value in [0, 1, 635, 21590, 29700, -100] {
+            assert_eq!(inches_to_hundredths_mm(hundredths_mm_to_inches(value)), value);
+            assert_eq!(mm_to_hundredths_mm(hundredths_mm_to_mm(value)), value);
+        }
+    }
+}
@@ -12,7 +12,7 @@ pub fn create_test_document() -> Vec<u8> {
 }
 
 pub fn cleanup_test_jobs() {
-    if let Ok(jobs) = get_active_jobs(None) {
+    if let Ok(jobs) = get_active_jobs(None, None) {
         for job in jobs {
             if job.title.contains("Test") || job.title.contains("Integration") {
                 println!("Cleaning up test job: {}", job.id);
@@ -103,14 +103,15 @@ fn test_integration_printer_capabilities() {
     match info_result {
         Ok(info) => {
             println!("Successfully got detailed info for {}", printer.name);
+            let raw_dest = printer.as_ptr();
             
             // Test media capabilities
-            let media_count = info.get_media_count(std::ptr::null_mut(), printer.as_ptr(), MEDIA_FLAGS_DEFAULT);
+            let media_count = info.get_media_count(std::ptr::null_mut(), raw_dest.as_ptr(), MEDIA_FLAGS_DEFAULT);
             println!("Media count: {}", media_count);
             
             if media_count > 0 {
                 // Test getting all media
-                let all_media = info.get_all_media(std::ptr::null_mut(), printer.as_ptr(), MEDIA_FLAGS_DEFAULT);
+                let all_media = info.get_all_media(std::ptr::null_mut(), raw_dest.as_ptr(), MEDIA_FLAGS_DEFAULT);
                 match all_media {
                     Ok(media_list) => {
                         println!("Found {} media sizes", media_list.len());
@@ -123,7 +124,7 @@ fn test_integration_printer_capabilities() {
                 }
                 
                 // Test getting default media
-                let default_media = info.get_default_media(std::ptr::null_mut(), printer.as_ptr(), MEDIA_FLAGS_DEFAULT);
+                let default_media = info.get_default_media(std::ptr::null_mut(), raw_dest.as_ptr(), MEDIA_FLAGS_DEFAULT);
                 match default_media {
                     Ok(media) => {
                         println!("Default media: {} ({:.1}\" x {:.1}\")", 
@@ -141,23 +142,6 @@ fn test_integration_printer_capabilities() {
             println!("Supports copies: {}", supports_copies);
             println!("Supports media: {}", supports_media);
             println!("Supports duplex: {}", supports_duplex);
-            
-            // Cleanup
-            unsafe {
-                let dest_ptr = printer.as_ptr();
-                if !dest_ptr.is_null() {
-                    let dest_box = Box::from_raw(dest_ptr);
-                    if !dest_box.name.is_null() {
-                        let _ = std::ffi::CString::from_raw(dest_box.name);
-                    }
-                    if !dest_box.instance.is_null() {
-                        let _ = std::ffi::CString::from_raw(dest_box.instance);
-                    }
-                    if !dest_box.options.is_null() {
-                        cups_rs::bindings::cupsFreeOptions(dest_box.num_options, dest_box.options);
-                    }
-                }
-            }
         }
         Err(e) => {
             println!("Could not get detailed info for {}: {}", printer.name, e);
@@ -290,6 +274,129 @@ fn test_integration_job_with_options() {
     }
 }
 
+#[test]
+#[serial]
+fn test_integration_safe_capability_queries() {
+    if !cups_available() { return; }
+
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    match printer.all_media() {
+        Ok(media) => {
+            println!("{} supports {} media sizes", printer.name, media.len());
+            if let Some(first) = media.first() {
+                let by_name = printer.media_by_name(&first.name).expect("Listed media should resolve");
+                assert_eq!(by_name.width, first.width);
+            }
+        }
+        Err(e) => println!("Could not list media: {}", e),
+    }
+
+    if let Ok(default_media) = printer.default_media() {
+        println!("Default media: {}", default_media.name);
+    }
+    if let Ok(values) = printer.supported_values(SIDES) {
+        println!("Supported sides: {:?}", values);
+    }
+}
+
+#[test]
+#[serial]
+fn test_integration_validate_options() {
+    if !cups_available() { return; }
+
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    let options = PrintOptions::new()
+        .copies(1)
+        .custom_option("print-color-mode", "not-a-real-mode");
+
+    match options.validate(&printer) {
+        Ok(unsupported) => {
+            println!("Unsupported options for {}: {:?}", printer.name, unsupported);
+            assert!(!unsupported.contains(&"copies".to_string()));
+            assert!(unsupported.contains(&"print-color-mode".to_string()));
+        }
+        Err(e) => println!("Could not validate options: {}", e),
+    }
+}
+
+#[test]
+#[serial]
+fn test_integration_job_with_connection() {
+    if !cups_available() { return; }
+
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    let connection = match printer.connect(ConnectionFlags::Scheduler, Some(5000), None) {
+        Ok(c) => c,
+        Err(e) => {
+            println!("Could not connect to {}: {}", printer.name, e);
+            return;
+        }
+    };
+
+    let job = match create_job_with_connection(&connection, &printer, "Connection Test Job", &PrintOptions::new()) {
+        Ok(j) => j,
+        Err(e) => {
+            println!("Could not create job over connection: {}", e);
+            return;
+        }
+    };
+
+    let content = "Submitted over an explicit connection";
+    match job.submit_reader_with_connection(&connection, content.as_bytes(), FORMAT_TEXT, "connection.txt", &[], true) {
+        Ok(()) => {
+            let _ = job.close_with_connection(&connection);
+        }
+        Err(e) => {
+            println!("Failed to submit over connection: {}", e);
+            let _ = job.cancel_with_connection(&connection);
+        }
+    }
+
+    let jobs = get_jobs_with_connection(&connection, Some(&printer.name), JobFilter::All)
+        .expect("Should list jobs over connection");
+    println!("{} jobs visible over connection", jobs.len());
+}
+
+#[test]
+#[serial]
+fn test_integration_connect_with_encryption() {
+    if !cups_available() { return; }
+
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    match printer.connect_with_encryption(
+        ConnectionFlags::Scheduler,
+        Some(5000),
+        None,
+        config::EncryptionMode::IfRequested,
+    ) {
+        Ok(connection) => {
+            assert!(connection.is_connected());
+            println!(
+                "Connected to {:?} (encrypted: {})",
+                connection.peer_address(),
+                connection.is_encrypted()
+            );
+        }
+        Err(e) => println!("Could not connect to {}: {}", printer.name, e),
+    }
+}
+
 #[test]
 #[serial]
 fn test_integration_job_cancellation() {
@@ -329,16 +436,20 @@ fn test_integration_get_jobs() {
     if !cups_available() { return; }
     
     // Test getting all jobs
-    let all_jobs = get_jobs(None).unwrap_or_default();
+    let all_jobs = get_jobs(None, None).unwrap_or_default();
     println!("Found {} total jobs", all_jobs.len());
     
     // Test getting active jobs
-    let active_jobs = get_active_jobs(None).unwrap_or_default();
+    let active_jobs = get_active_jobs(None, None).unwrap_or_default();
     println!("Found {} active jobs", active_jobs.len());
     
     // Test getting completed jobs
-    let completed_jobs = get_completed_jobs(None).unwrap_or_default();
+    let completed_jobs = get_completed_jobs(None, None).unwrap_or_default();
     println!("Found {} completed jobs", completed_jobs.len());
+
+    // Test getting only the current user's jobs
+    let my_jobs = get_my_jobs(None).expect("Should list the current user's jobs");
+    println!("Found {} jobs owned by the current user", my_jobs.len());
     
     // Print some job details
     for job in active_jobs.iter().take(3) {
@@ -399,4 +510,766 @@ fn test_integration_error_handling() {
     if let Err(e) = result {
         println!("Expected error for canceling non-existent job: {}", e);
     }
-}
\ No newline at end of file
+}
+#[test]
+#[serial]
+fn test_integration_submit_reader() {
+    if !cups_available() { return; }
+    
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+    
+    let job = match create_job(&printer, "Streaming Test Job") {
+        Ok(j) => j,
+        Err(e) => {
+            println!("Could not create job for streaming test: {}", e);
+            return;
+        }
+    };
+    
+    // Larger than a single write chunk so the document is sent in pieces
+    let content = "Streamed line for CUPS integration testing.\n".repeat(1000);
+    let reader = std::io::Cursor::new(content.into_bytes());
+    
+    match job.submit_reader(reader, FORMAT_TEXT, "streaming_test.txt") {
+        Ok(()) => {
+            println!("Streamed document to job {}", job.id);
+            let _ = job.close();
+        }
+        Err(e) => {
+            println!("Failed to stream document: {}", e);
+            let _ = job.cancel();
+        }
+    }
+}
+
+#[test]
+#[serial]
+fn test_integration_submit_with_progress() {
+    if !cups_available() { return; }
+    
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+    
+    let job = match create_job(&printer, "Progress Test Job") {
+        Ok(j) => j,
+        Err(e) => {
+            println!("Could not create job for progress test: {}", e);
+            return;
+        }
+    };
+    
+    let content = "Progress reporting line for CUPS integration testing.\n".repeat(500);
+    let mut last_progress = (0, 0);
+    
+    let result = job.submit_data_with_progress(
+        content.as_bytes(),
+        FORMAT_TEXT,
+        "progress_test.txt",
+        |written, total| last_progress = (written, total),
+    );
+    
+    match result {
+        Ok(()) => {
+            assert_eq!(last_progress, (content.len(), content.len()));
+            let _ = job.close();
+        }
+        Err(e) => {
+            println!("Failed to submit document with progress: {}", e);
+            let _ = job.cancel();
+        }
+    }
+}
+
+#[test]
+#[serial]
+fn test_integration_pause_resume() {
+    if !cups_available() { return; }
+
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    match printer.pause() {
+        Ok(()) => {
+            let paused = get_destination(&printer.name).expect("Printer should still exist");
+            println!("Paused {} (reported paused: {})", printer.name, paused.is_paused());
+            printer.resume().expect("Should resume a printer it was allowed to pause");
+        }
+        Err(e) => println!("Could not pause {} (admin rights needed): {}", printer.name, e),
+    }
+}
+
+/// Puts a printer back into accepting jobs when dropped, even if the test panics
+struct AcceptJobsGuard<'a>(&'a Destination);
+
+impl Drop for AcceptJobsGuard<'_> {
+    fn drop(&mut self) {
+        if let Err(e) = self.0.set_accepting_jobs(true, None) {
+            eprintln!("Failed to make {} accept jobs again: {}", self.0.name, e);
+        }
+    }
+}
+
+#[test]
+#[serial]
+fn test_integration_set_accepting_jobs() {
+    if !cups_available() { return; }
+
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+    if !printer.is_accepting_jobs() {
+        println!("{} is already rejecting jobs - skipping", printer.name);
+        return;
+    }
+
+    match printer.set_accepting_jobs(false, Some("Integration test")) {
+        Ok(()) => {
+            let _guard = AcceptJobsGuard(&printer);
+            let rejecting = get_destination(&printer.name).expect("Printer should still exist");
+            assert!(!rejecting.is_accepting_jobs(), "Printer should be rejecting jobs");
+        }
+        Err(Error::PermissionDenied(_)) | Err(Error::AuthenticationRequired(_)) => {
+            println!("Admin rights needed to change {}", printer.name)
+        }
+        Err(e) => println!("Could not reject jobs on {}: {}", printer.name, e),
+    }
+
+    let accepting = get_destination(&printer.name).expect("Printer should still exist");
+    assert!(accepting.is_accepting_jobs(), "Printer should accept jobs again");
+}
+
+#[test]
+#[serial]
+fn test_integration_refresh_destination() {
+    if !cups_available() { return; }
+
+    let mut printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    printer.options.insert("printer-state".to_string(), "stale".to_string());
+    printer.refresh().expect("Should refresh an existing destination");
+    assert_ne!(printer.options.get("printer-state").map(String::as_str), Some("stale"));
+
+    let mut missing = printer.clone();
+    missing.name = "cups-rs-missing-printer".to_string();
+    assert!(matches!(missing.refresh(), Err(Error::DestinationNotFound(_))));
+}
+
+#[test]
+#[serial]
+fn test_integration_get_named_destination() {
+    if !cups_available() { return; }
+
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    let named = get_named_destination(None, &printer.name, None)
+        .expect("Should find an existing destination by name");
+    assert_eq!(named.name, printer.name);
+
+    let connection = printer
+        .connect(ConnectionFlags::Scheduler, Some(5000), None)
+        .expect("Should connect to the scheduler");
+    let named = get_named_destination(Some(&connection), &printer.name, None)
+        .expect("Should find the destination over an explicit connection");
+    assert_eq!(named.name, printer.name);
+
+    assert!(matches!(
+        get_named_destination(None, "cups-rs-missing-printer", None),
+        Err(Error::DestinationNotFound(_))
+    ));
+}
+
+#[test]
+#[serial]
+fn test_integration_discover_destinations() {
+    if !cups_available() { return; }
+
+    let stream = discover_destinations(Duration::from_secs(5), 0, 0);
+    let first = stream.next_timeout(Duration::from_secs(5));
+    if let Some(dest) = &first {
+        assert!(!dest.name.is_empty());
+    }
+
+    stream.cancel();
+    drop(stream);
+
+    // Iterating runs to the end of the discovery window instead of forever
+    let start = std::time::Instant::now();
+    for dest in discover_destinations(Duration::from_secs(1), 0, 0) {
+        assert!(!dest.name.is_empty());
+    }
+    assert!(start.elapsed() < Duration::from_secs(30));
+}
+
+#[test]
+#[serial]
+fn test_integration_get_ppd() {
+    if !cups_available() { return; }
+
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    match printer.get_ppd() {
+        Ok(path) => {
+            assert!(path.exists(), "PPD should be downloaded to {:?}", path);
+            remove_ppd(&path).expect("Should remove the temporary PPD");
+            assert!(!path.exists());
+        }
+        Err(Error::UnsupportedFeature(_)) => println!("{} has no PPD", printer.name),
+        Err(e) => println!("PPD download failed: {}", e),
+    }
+}
+
+#[test]
+#[serial]
+fn test_integration_supported_formats() {
+    if !cups_available() { return; }
+
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    let job = match create_job(&printer, "Format Test Job") {
+        Ok(j) => j,
+        Err(e) => {
+            println!("Could not create job for format test: {}", e);
+            return;
+        }
+    };
+
+    match job.supported_formats() {
+        Ok(formats) => {
+            println!("{} accepts: {}", printer.name, formats.join(", "));
+            assert!(formats.iter().all(|f| f.contains('/')));
+        }
+        Err(e) => println!("Could not query supported formats: {}", e),
+    }
+
+    let _ = job.cancel();
+}
+
+#[test]
+#[serial]
+fn test_integration_subscription() {
+    if !cups_available() { return; }
+
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    let subscription = match Subscription::create(
+        &printer,
+        &[SubscriptionEvent::JobCreated, SubscriptionEvent::PrinterStateChanged],
+        Some(Duration::from_secs(60)),
+    ) {
+        Ok(s) => s,
+        Err(e) => {
+            println!("Could not create subscription: {}", e);
+            return;
+        }
+    };
+    assert!(subscription.id() > 0);
+
+    if let Ok(job) = create_job(&printer, "Subscription Test Job") {
+        let _ = job.cancel();
+    }
+
+    match subscription.get_notifications() {
+        Ok(notifications) => {
+            for notification in &notifications {
+                println!("{}: {:?}", notification.event, notification.text);
+            }
+        }
+        Err(e) => println!("Could not fetch notifications: {}", e),
+    }
+
+    subscription.cancel().expect("Should cancel the subscription");
+}
+
+#[test]
+#[serial]
+fn test_integration_default_destination() {
+    if !cups_available() { return; }
+
+    match get_default_destination() {
+        Ok(default) => {
+            assert!(default.is_default);
+            assert!(!default.name.is_empty());
+        }
+        Err(e) => println!("No default destination configured: {}", e),
+    }
+}
+
+#[test]
+#[serial]
+fn test_integration_resolve_conflicts() {
+    if !cups_available() { return; }
+
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    let info = match printer.get_detailed_info(std::ptr::null_mut()) {
+        Ok(info) => info,
+        Err(e) => {
+            println!("Could not get detailed info: {}", e);
+            return;
+        }
+    };
+
+    let options = vec![
+        ("copies".to_string(), "2".to_string()),
+        ("copies".to_string(), "1".to_string()),
+    ];
+    match info.resolve_conflicts(&printer, &options) {
+        Ok(resolved) => {
+            let copies: Vec<_> = resolved.iter().filter(|(name, _)| name == "copies").collect();
+            assert_eq!(copies.len(), 1);
+        }
+        Err(e) => println!("Could not resolve options: {}", e),
+    }
+}
+
+#[test]
+#[serial]
+fn test_integration_destination_new_request() {
+    if !cups_available() { return; }
+
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    let (request, connection) = match printer.new_request(IppOperation::GetPrinterAttributes) {
+        Ok(pair) => pair,
+        Err(e) => {
+            println!("Could not start request: {}", e);
+            return;
+        }
+    };
+
+    let response = request
+        .send(&connection, connection.resource_path())
+        .expect("Should send Get-Printer-Attributes");
+    assert!(response.is_successful());
+    assert!(response.find_attribute("printer-name", None).is_some());
+}
+
+#[test]
+#[serial]
+fn test_integration_get_all_destinations_timeout() {
+    if !cups_available() { return; }
+
+    let start = std::time::Instant::now();
+    let destinations = get_all_destinations_timeout(1000)
+        .expect("Should enumerate destinations with a timeout");
+    println!("Found {} destinations in {:?}", destinations.len(), start.elapsed());
+
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+#[serial]
+fn test_integration_set_job_attributes() {
+    if !cups_available() { return; }
+
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    let options = PrintOptions::new().hold_until(JOB_HOLD_UNTIL_INDEFINITE);
+    let job = match create_job_with_options(&printer, "Set Attributes Test Job", &options) {
+        Ok(j) => j,
+        Err(e) => {
+            println!("Could not create job: {}", e);
+            return;
+        }
+    };
+
+    let result = job.set_attributes(&[("job-priority".to_string(), "90".to_string())]);
+    match result {
+        Ok(()) => {
+            if let Ok(info) = get_job_info(job.id) {
+                assert_eq!(info.priority, 90);
+            }
+        }
+        Err(e) => println!("Server refused to update job {}: {}", job.id, e),
+    }
+
+    assert!(matches!(job.set_attributes(&[]), Err(Error::ConfigurationError(_))));
+    let _ = job.cancel();
+}
+
+#[test]
+#[serial]
+fn test_integration_media_with_flags() {
+    if !cups_available() { return; }
+
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    let count = match printer.media_count(MEDIA_FLAGS_DEFAULT) {
+        Ok(count) => count,
+        Err(e) => {
+            println!("Could not get media count: {}", e);
+            return;
+        }
+    };
+    println!("Media count: {}", count);
+
+    if let Ok(all_media) = printer.all_media_with_flags(MEDIA_FLAGS_DEFAULT) {
+        assert!(all_media.len() <= count);
+    }
+    if count > 0 {
+        let first = printer.media_by_index(0, MEDIA_FLAGS_DEFAULT).expect("Should get media 0");
+        assert!(!first.name.is_empty());
+    }
+    assert!(printer.media_by_index(count, MEDIA_FLAGS_DEFAULT).is_err());
+
+    if let Ok(media) = printer.default_media_with_flags(MEDIA_FLAGS_READY) {
+        println!("Default ready media: {}", media.name);
+    }
+}
+
+#[test]
+#[serial]
+fn test_integration_print_document_uri() {
+    if !cups_available() { return; }
+
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    let mut temp_file = NamedTempFile::new().unwrap();
+    writeln!(temp_file, "Fetched by the server").unwrap();
+    temp_file.flush().unwrap();
+    let doc_uri = format!("file://{}", temp_file.path().display());
+
+    match create_job_with_document_uri(&printer, "Print-URI Test Job", &doc_uri, FORMAT_TEXT) {
+        Ok(job) => {
+            println!("Server accepted Print-URI as job {}", job.id);
+            let _ = job.cancel();
+        }
+        Err(Error::UnsupportedFeature(e)) => println!("Print-URI disabled: {}", e),
+        Err(e) => println!("Print-URI failed: {}", e),
+    }
+}
+
+#[test]
+#[serial]
+fn test_integration_print_file_one_shot() {
+    if !cups_available() { return; }
+
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    let mut temp_file = NamedTempFile::new().unwrap();
+    writeln!(temp_file, "One-shot print test").unwrap();
+    temp_file.flush().unwrap();
+
+    match print_file(&printer, temp_file.path(), &PrintOptions::new()) {
+        Ok(job) => {
+            println!("Printed file as job {}", job.id);
+            let _ = job.cancel();
+        }
+        Err(e) => println!("One-shot file print failed: {}", e),
+    }
+
+    match print_data(&printer, b"One-shot data", FORMAT_TEXT, "data.txt", &PrintOptions::new()) {
+        Ok(job) => {
+            println!("Printed data as job {}", job.id);
+            let _ = job.cancel();
+        }
+        Err(e) => println!("One-shot data print failed: {}", e),
+    }
+}
+
+#[test]
+#[serial]
+fn test_integration_max_document_size() {
+    if !cups_available() { return; }
+
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    match printer.max_document_size() {
+        Ok(Some(limit)) => println!("{} accepts documents up to {} bytes", printer.name, limit),
+        Ok(None) => println!("{} does not advertise a size limit", printer.name),
+        Err(e) => println!("Could not query size limit: {}", e),
+    }
+
+    let job = match create_job(&printer, "Size Limit Test") {
+        Ok(job) => job.with_max_document_size(16),
+        Err(_) => return,
+    };
+
+    let result = job.submit_data(&[b'x'; 64], FORMAT_TEXT, "too-large.txt");
+    if let Err(Error::DocumentTooLarge(size, limit)) = result {
+        assert_eq!((size, limit), (64, 16));
+    }
+    let _ = job.cancel();
+}
+
+#[test]
+#[serial]
+fn test_integration_job_from_id() {
+    if !cups_available() { return; }
+
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    let job = match create_job(&printer, "From Id Test") {
+        Ok(job) => job,
+        Err(_) => return,
+    };
+
+    match Job::from_id(job.id, &printer.name) {
+        Ok(found) => {
+            assert_eq!(found.id, job.id);
+            assert_eq!(found.uri(), job.uri());
+            println!("Recovered job {} ({})", found.id, found.title);
+        }
+        Err(e) => println!("Could not recover job {}: {}", job.id, e),
+    }
+
+    assert!(Job::from_id(job.id, "NoSuchPrinter").is_err());
+    let _ = job.cancel();
+}
+
+#[test]
+#[serial]
+fn test_integration_job_query() {
+    if !cups_available() { return; }
+
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    let query = JobQuery::new(Some(&printer.name))
+        .which_jobs(JobFilter::All)
+        .limit(5)
+        .requested_attributes(&["job-name", "job-state", "job-originating-host-name"]);
+
+    match query.run() {
+        Ok(jobs) => {
+            assert!(jobs.len() <= 5);
+            for job in &jobs {
+                println!("Job {}: {} {:?}", job.id, job.title, job.extra);
+            }
+        }
+        Err(e) => println!("Job query failed: {}", e),
+    }
+}
+
+#[test]
+#[serial]
+fn test_integration_send_into_reuses_request() {
+    if !cups_available() { return; }
+
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    let (mut request, connection) = match printer.new_request(IppOperation::GetPrinterAttributes) {
+        Ok(pair) => pair,
+        Err(_) => return,
+    };
+    request
+        .add_string(IppTag::Operation, IppValueTag::Keyword, "requested-attributes", "printer-state")
+        .unwrap();
+
+    let mut response: Option<IppResponse> = None;
+    for _ in 0..3 {
+        match request.send_into(&connection, connection.resource_path(), &mut response) {
+            Ok(reply) => assert!(reply.find_attribute("printer-state", None).is_some()),
+            Err(e) => {
+                println!("Polling failed: {}", e);
+                return;
+            }
+        }
+    }
+    assert!(response.is_some());
+}
+
+#[test]
+#[serial]
+fn test_integration_recent_jobs() {
+    if !cups_available() { return; }
+
+    match get_recent_jobs(None, JobFilter::Completed, Some(3)) {
+        Ok(jobs) => {
+            assert!(jobs.len() <= 3);
+            for job in &jobs {
+                println!("Recent job {}: {} ({})", job.id, job.title, job.status);
+            }
+        }
+        Err(e) => println!("Could not list recent jobs: {}", e),
+    }
+}
+
+#[test]
+#[serial]
+fn test_integration_jobs_filtered() {
+    if !cups_available() { return; }
+
+    match get_jobs_filtered(None, JobFilter::Active) {
+        Ok(jobs) => {
+            for job in &jobs {
+                assert!(!job.status.is_terminal());
+            }
+            println!("Active jobs: {}", jobs.len());
+        }
+        Err(e) => println!("Could not list active jobs: {}", e),
+    }
+}
+
+#[test]
+#[serial]
+fn test_integration_printer_defaults() {
+    if !cups_available() { return; }
+
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    match printer.defaults() {
+        Ok(defaults) => {
+            if let Some(copies) = defaults.copies {
+                assert!(copies >= 1);
+            }
+            println!("{} defaults: {:?}", printer.name, defaults);
+        }
+        Err(e) => println!("Could not read defaults: {}", e),
+    }
+}
+
+#[test]
+#[serial]
+fn test_integration_accepts_format() {
+    if !cups_available() { return; }
+
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    match printer.accepts_format(FORMAT_PDF) {
+        Ok(accepted) => println!("{} accepts PDF: {}", printer.name, accepted),
+        Err(e) => {
+            println!("Could not query formats: {}", e);
+            return;
+        }
+    }
+
+    assert!(printer.accepts_format(FORMAT_RAW).unwrap());
+    assert!(!printer.accepts_format("application/x-not-a-format").unwrap());
+}
+
+#[test]
+#[serial]
+fn test_integration_copies_range() {
+    if !cups_available() { return; }
+
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    let Ok(Some((min, max))) = printer.copies_range() else {
+        println!("{} does not report copies-supported", printer.name);
+        return;
+    };
+    println!("{} accepts {} to {} copies", printer.name, min, max);
+    assert!(min <= max);
+
+    let too_many = PrintOptions::new().copies(max as u32 + 1);
+    if let Ok(unsupported) = too_many.validate(&printer) {
+        assert_eq!(unsupported, vec!["copies".to_string()]);
+    }
+}
+
+#[test]
+#[serial]
+fn test_integration_all_supported() {
+    if !cups_available() { return; }
+
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    let supported = match printer.all_supported() {
+        Ok(map) => map,
+        Err(e) => {
+            println!("Could not get supported options: {}", e);
+            return;
+        }
+    };
+
+    let options = printer.supported_options().unwrap_or_default();
+    for option in &options {
+        assert!(supported.contains_key(option));
+    }
+    if let Some(media) = supported.get(MEDIA) {
+        println!("{} media values supported", media.len());
+    }
+}
+
+#[test]
+#[serial]
+fn test_integration_localized_options() {
+    if !cups_available() { return; }
+
+    let printer = match get_test_printer() {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    match printer.localized_options() {
+        Ok(labels) => {
+            for (option, label) in labels.iter().take(5) {
+                println!("{} -> {}", option, label);
+                assert!(!label.is_empty());
+            }
+        }
+        Err(e) => println!("Could not localize options: {}", e),
+    }
+
+    if let Ok(values) = printer.localized_values(SIDES) {
+        let supported = printer.supported_values(SIDES).unwrap_or_default();
+        for value in &supported {
+            assert!(values.contains_key(value));
+        }
+    }
+}